@@ -0,0 +1,125 @@
+use std::path::PathBuf;
+
+use crate::scenes::game_state::{replay::Replay, GameConfig, RunMode};
+
+/// The description of all command line arguments, printed on --help or invalid arguments.
+pub const USAGE: &str = "Usage: radish [OPTIONS]
+
+Options:
+  --config <FILE>          Start a run from the game config in FILE, skipping the main menu.
+  --debug                  Start a run with the debug config, skipping the main menu.
+  --seed <SEED>            Set the seed of the run started, at most 4294967295. Starts a default run if no other run is chosen.
+  --replay <FILE|latest>   Play back the replay in FILE, or the most recent one. Replays keep their own seed.
+  --data-dir <DIR>         Read all data files from DIR instead of ./data.
+  --portable               Keep save files in the data folder instead of the user data folder.
+  --window-size <WxH>      Open the window with a width of W and a height of H pixels.
+  --help                   Print this message and exit.";
+
+/// The options the game was launched with from the command line.
+#[derive(Debug, Default)]
+pub struct LaunchArgs {
+    /// Wether only the usage should be printed.
+    pub help: bool,
+    /// The path of a game config to start a run from.
+    pub config: Option<PathBuf>,
+    /// Wether a run should be started with the debug config.
+    pub debug: bool,
+    /// The seed of the run started.
+    pub seed: Option<u64>,
+    /// The replay to play back, either a path or "latest".
+    pub replay: Option<String>,
+    /// The folder data files are read from.
+    pub data_dir: Option<PathBuf>,
+    /// Wether save files should be kept in the data folder instead of the user data folder.
+    pub portable: bool,
+    /// The width and height of the window.
+    pub window_size: Option<(f32, f32)>,
+}
+
+impl LaunchArgs {
+    /// Parses the passed command line arguments, excluding the name of the program.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut launch_args = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("Missing value for {}.", arg))
+            };
+            match arg.as_str() {
+                "--help" | "-h" => launch_args.help = true,
+                "--config" => launch_args.config = Some(PathBuf::from(value()?)),
+                "--debug" => launch_args.debug = true,
+                "--seed" => {
+                    let seed = value()?;
+                    launch_args.seed = Some(
+                        // seeds are kept short, as described in GameConfig::random_seed
+                        seed.parse::<u32>()
+                            .map(u64::from)
+                            .map_err(|_| format!("Invalid seed {}.", seed))?,
+                    );
+                }
+                "--replay" => launch_args.replay = Some(value()?),
+                "--data-dir" => launch_args.data_dir = Some(PathBuf::from(value()?)),
+                "--portable" => launch_args.portable = true,
+                "--window-size" => {
+                    let size = value()?;
+                    launch_args.window_size = Some(
+                        size.split_once('x')
+                            .and_then(|(w, h)| Some((w.parse().ok()?, h.parse().ok()?)))
+                            .filter(|&(w, h): &(f32, f32)| w > 0. && h > 0.)
+                            .ok_or_else(|| format!("Invalid window size {}.", size))?,
+                    );
+                }
+                _ => return Err(format!("Unknown argument {}.", arg)),
+            }
+        }
+
+        if launch_args.config.is_some() && launch_args.debug {
+            return Err("--config and --debug can't be combined.".to_owned());
+        }
+        if launch_args.replay.is_some()
+            && (launch_args.config.is_some() || launch_args.debug || launch_args.seed.is_some())
+        {
+            return Err("--replay can't be combined with --config, --debug or --seed.".to_owned());
+        }
+
+        Ok(launch_args)
+    }
+
+    /// Returns the config of the run to start directly, if one was requested.
+    pub fn start_config(&self) -> Result<Option<GameConfig>, String> {
+        let config = if let Some(path) = &self.config {
+            Some(GameConfig {
+                mode: RunMode::Custom,
+                ..GameConfig::from_path(path)
+                    .map_err(|e| format!("Could not load config {}: {}", path.display(), e))?
+            })
+        } else if self.debug {
+            Some(GameConfig::debug())
+        } else if self.seed.is_some() {
+            Some(GameConfig::default())
+        } else {
+            None
+        };
+
+        Ok(config.map(|config| GameConfig {
+            seed: self.seed.or(config.seed),
+            ..config
+        }))
+    }
+
+    /// Returns the replay to play back, if one was requested.
+    pub fn start_replay(&self) -> Result<Option<Replay>, String> {
+        match self.replay.as_deref() {
+            None => Ok(None),
+            Some("latest") => Replay::latest()
+                .map(Some)
+                .ok_or_else(|| "No replay has been saved yet.".to_owned()),
+            Some(path) => Replay::from_path(path)
+                .map(Some)
+                .map_err(|e| format!("Could not load replay {}: {}", path, e)),
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+
+/// Loads and parses the data file at the passed path.
+pub fn from_path<T: DeserializeOwned>(
+    path: impl AsRef<Path>,
+) -> Result<T, Box<dyn std::error::Error>> {
+    let string = std::fs::read_to_string(
+        path.as_ref()
+            .to_str()
+            .ok_or_else(|| ggez::GameError::CustomError("Could not read path.".to_owned()))?,
+    )?;
+    Ok(toml::from_str(&string)?)
+}
+
+/// Loads all data files from the passed folder, ordered by file name.
+/// Files that fail to load are reported as the passed kind of definition and skipped.
+pub fn load_folder<T: DeserializeOwned>(path: impl AsRef<Path>, kind: &str) -> Vec<T> {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(path)
+        .map(|dir| {
+            dir.filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().map(|ext| ext == "toml").unwrap_or(false))
+                .collect()
+        })
+        .unwrap_or_default();
+    paths.sort();
+
+    paths
+        .iter()
+        .filter_map(|path| match from_path(path) {
+            Ok(definition) => Some(definition),
+            Err(e) => {
+                println!(
+                    "[ERROR/Radish] Could not load {} {}: {}",
+                    kind,
+                    path.display(),
+                    e
+                );
+                None
+            }
+        })
+        .collect()
+}
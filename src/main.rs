@@ -5,9 +5,13 @@
 
 use ggez::*;
 
+mod args;
+mod data;
 mod music;
 mod options;
+mod paths;
 mod scenes;
+mod storage;
 
 const WIDTH: f32 = 1200.;
 const HEIGHT: f32 = 900.;
@@ -21,6 +25,32 @@ fn main() -> GameResult {
     // for debugging
     std::env::set_var("RUST_BACKTRACE", "full");
 
+    // read command line arguments
+
+    let launch_args = match args::LaunchArgs::parse(std::env::args().skip(1)) {
+        Ok(launch_args) => launch_args,
+        Err(e) => {
+            println!("[ERROR/Radish] {}\n\n{}", e, args::USAGE);
+            return Ok(());
+        }
+    };
+    if launch_args.help {
+        println!("{}", args::USAGE);
+        return Ok(());
+    }
+    paths::init(launch_args.data_dir.clone(), launch_args.portable);
+    let (width, height) = launch_args.window_size.unwrap_or((WIDTH, HEIGHT));
+
+    // load the run requested, if any
+
+    let (replay, config) = match (launch_args.start_replay(), launch_args.start_config()) {
+        (Ok(replay), Ok(config)) => (replay, config),
+        (Err(e), _) | (_, Err(e)) => {
+            println!("[ERROR/Radish] {}", e);
+            return Ok(());
+        }
+    };
+
     //generate game context (window etc.)
     let (mut ctx, event_loop): (ggez::context::Context, ggez::event::EventLoop<()>) =
         ContextBuilder::new("radish", "Linus Mußmächer")
@@ -34,7 +64,7 @@ fn main() -> GameResult {
                 conf::WindowMode::default()
                     .fullscreen_type(conf::FullscreenType::Windowed)
                     .resizable(true)
-                    .dimensions(WIDTH, HEIGHT),
+                    .dimensions(width, height),
             )
             .build()?;
 
@@ -49,11 +79,20 @@ fn main() -> GameResult {
         graphics::FontData::from_path(&ctx, "/fonts/retro_mono.otf")?,
     );
 
-    // create Start Scene
-
-    let start_scene = scenes::main_menu::MainMenu::new(&ctx)?;
+    // create Start Scene: a replay or run requested from the command line, or the main menu
 
     //create Scene Manager
 
+    if let Some(replay) = replay {
+        let start_scene = scenes::game_state::GameState::from_replay(&ctx, replay)?;
+        mooeye::scene_manager::SceneManager::new_and_run(event_loop, ctx, start_scene);
+    }
+
+    if let Some(config) = config {
+        let start_scene = scenes::game_state::GameState::new(&ctx, config)?;
+        mooeye::scene_manager::SceneManager::new_and_run(event_loop, ctx, start_scene);
+    }
+
+    let start_scene = scenes::main_menu::MainMenu::new(&ctx)?;
     mooeye::scene_manager::SceneManager::new_and_run(event_loop, ctx, start_scene);
 }
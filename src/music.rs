@@ -79,10 +79,7 @@ impl MusicPlayer {
 
     /// Checks for changes in the options file to change music volume if neccessary.
     pub fn poll_options(&mut self) {
-        self.volume = crate::options::OPTIONS
-            .with(|opt| *opt.borrow())
-            .music_volume as f32
-            / 100.
-            * 0.15;
+        self.volume =
+            crate::options::OPTIONS.with(|opt| opt.borrow().music_volume) as f32 / 100. * 0.15;
     }
 }
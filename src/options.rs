@@ -1,13 +1,13 @@
-use std::{cell::RefCell, fs, path::Path};
+use std::{cell::RefCell, path::Path};
 
 use serde::{Deserialize, Serialize};
 
 thread_local! {
-    pub static OPTIONS: RefCell<OptionsConfig> = RefCell::new(OptionsConfig::from_path("./data/options.toml").unwrap_or_default());
+    pub static OPTIONS: RefCell<OptionsConfig> = RefCell::new(crate::storage::load(crate::paths::save_path("options.toml"), "options").unwrap_or_default());
 }
 
 /// A struct that represents the game options.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OptionsConfig {
     /// The volume of sound effects played by the game.
     pub volume: u8,
@@ -15,28 +15,24 @@ pub struct OptionsConfig {
     pub music_volume: u8,
     /// Wether or not to display tutorial hints
     pub tutorial: bool,
+    /// The name last entered into the highscore list, suggested for the next entry.
+    #[serde(default = "default_player_name")]
+    pub player_name: String,
 }
 
-impl OptionsConfig {
-    /// Loads an option config from the given path.
-    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
-        let string = fs::read_to_string(
-            path.as_ref()
-                .to_str()
-                .ok_or_else(|| ggez::GameError::CustomError("Could not read path.".to_owned()))?,
-        )?;
-        Ok(toml::from_str(&string)?)
-    }
+/// Helper function for options from before player names were introduced.
+fn default_player_name() -> String {
+    "Player".to_owned()
+}
 
+impl crate::storage::SaveFile for OptionsConfig {
+    const VERSION: u32 = 1;
+}
+
+impl OptionsConfig {
     /// Saves this option config to the given path.
     pub fn save_to_file(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn std::error::Error>> {
-        fs::write(
-            path.as_ref()
-                .to_str()
-                .ok_or_else(|| ggez::GameError::CustomError("Could not read path.".to_owned()))?,
-            toml::to_string(&self)?,
-        )?;
-        Ok(())
+        crate::storage::save(path, self)
     }
 }
 
@@ -46,6 +42,7 @@ impl Default for OptionsConfig {
             volume: 50,
             music_volume: 50,
             tutorial: true,
+            player_name: default_player_name(),
         }
     }
 }
@@ -53,7 +50,11 @@ impl Default for OptionsConfig {
 pub fn save_options() {
     // save options to file on game exit (when the main menu is dropped)
     crate::options::OPTIONS.with(|opt| {
-        if opt.borrow().save_to_file("./data/options.toml").is_err() {
+        if opt
+            .borrow()
+            .save_to_file(crate::paths::save_path("options.toml"))
+            .is_err()
+        {
             println!("[ERROR/Radish] Could not save options.")
         };
     });
@@ -0,0 +1,139 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+/// The folder all data files are read from, unless another one is chosen on startup.
+const DEFAULT_DATA_DIR: &str = "./data";
+/// The name of the folder save files are kept in inside the user data folder of the platform.
+const SAVE_DIR_NAME: &str = "radish";
+/// A file that, if present in the data folder, makes the game keep its save files in the data folder.
+const PORTABLE_MARKER: &str = "portable";
+/// The save files and folders of earlier versions, which kept them in the data folder.
+const MIGRATED_FILES: [&str; 9] = [
+    "options.toml",
+    "keymap.toml",
+    "achievements.toml",
+    "highscores.toml",
+    "daily_highscores.toml",
+    "campaign_records.toml",
+    "savegame.toml",
+    "replays",
+    "presets",
+];
+
+/// The folder the definitions of spells, enemies, buildings etc. are read from. Set once on startup.
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+/// The folder options, progress and saved runs are read from and written to. Set once on startup.
+static SAVE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Chooses the folders data and save files are read from and written to. Needs to be called before any file is loaded.
+/// In portable mode, save files are kept in the data folder. Otherwise, they are kept in the user data folder of the platform,
+/// and the save files of earlier versions are copied over from the data folder on first launch.
+/// If the user data folder can't be found or created, the game falls back to portable mode.
+pub fn init(data_dir: Option<PathBuf>, portable: bool) {
+    let data_dir = data_dir.unwrap_or_else(|| PathBuf::from(DEFAULT_DATA_DIR));
+    let portable = portable || data_dir.join(PORTABLE_MARKER).exists();
+
+    let save_dir = if portable {
+        data_dir.clone()
+    } else {
+        match user_save_dir() {
+            Some(save_dir) => {
+                let first_launch = !save_dir.exists();
+                match std::fs::create_dir_all(&save_dir) {
+                    Ok(()) => {
+                        if first_launch {
+                            migrate(&data_dir, &save_dir);
+                        }
+                        save_dir
+                    }
+                    Err(e) => {
+                        println!(
+                            "[WARNING] Could not create save folder {}, keeping save files in the data folder: {}",
+                            save_dir.display(),
+                            e
+                        );
+                        data_dir.clone()
+                    }
+                }
+            }
+            None => {
+                println!("[WARNING] Could not find a user data folder, keeping save files in the data folder.");
+                data_dir.clone()
+            }
+        }
+    };
+
+    if DATA_DIR.set(data_dir).is_err() || SAVE_DIR.set(save_dir).is_err() {
+        println!(
+            "[WARNING] The data and save folders can't be changed once files have been loaded."
+        );
+    }
+}
+
+/// Returns the path of the passed file or folder inside the data folder, containing the definitions of spells, enemies etc.
+/// Absolute paths are returned unchanged.
+pub fn data_path(file: impl AsRef<Path>) -> PathBuf {
+    DATA_DIR
+        .get_or_init(|| PathBuf::from(DEFAULT_DATA_DIR))
+        .join(file)
+}
+
+/// Returns the path of the passed file or folder inside the save folder, containing options, progress and saved runs.
+/// Absolute paths are returned unchanged.
+pub fn save_path(file: impl AsRef<Path>) -> PathBuf {
+    SAVE_DIR
+        .get_or_init(|| PathBuf::from(DEFAULT_DATA_DIR))
+        .join(file)
+}
+
+/// Returns the folder save files are kept in inside the user data folder of the platform, if that can be found.
+fn user_save_dir() -> Option<PathBuf> {
+    let env_dir = |var: &str| {
+        std::env::var_os(var)
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+    };
+
+    let user_dir = if cfg!(target_os = "windows") {
+        env_dir("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        env_dir("HOME").map(|home| home.join("Library").join("Application Support"))
+    } else {
+        env_dir("XDG_DATA_HOME")
+            .or_else(|| env_dir("HOME").map(|home| home.join(".local").join("share")))
+    }?;
+
+    Some(user_dir.join(SAVE_DIR_NAME))
+}
+
+/// Copies the save files of earlier versions from the data folder to the save folder.
+fn migrate(data_dir: &Path, save_dir: &Path) {
+    for file in MIGRATED_FILES {
+        let from = data_dir.join(file);
+        if from.exists() {
+            if let Err(e) = copy_recursively(&from, &save_dir.join(file)) {
+                println!(
+                    "[ERROR/Radish] Could not migrate {} to the save folder: {}",
+                    from.display(),
+                    e
+                );
+            }
+        }
+    }
+}
+
+/// Copies the passed file, or the passed folder with all its contents.
+fn copy_recursively(from: &Path, to: &Path) -> std::io::Result<()> {
+    if from.is_dir() {
+        std::fs::create_dir_all(to)?;
+        for entry in std::fs::read_dir(from)? {
+            let entry = entry?;
+            copy_recursively(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        std::fs::copy(from, to).map(|_| ())
+    }
+}
@@ -1,39 +1,83 @@
-use super::game_state::achievements;
-use ggez::{graphics, GameError};
+use std::{collections::BTreeMap, hash::Hash};
+
+use super::game_state::{
+    achievements,
+    run_stats::{by_name, RunStats, StatNames},
+    GameConfig, RunMode,
+};
+use ggez::{graphics, winit::event::VirtualKeyCode, GameError};
 use mooeye::{scene_manager, ui, ui::UiContainer, ui::UiContent};
 
 use crate::music;
 use crate::PALETTE;
 
+const NAME_CONTAINER_ID: u32 = 10;
+const NAME_ID: u32 = 11;
+
+/// The maximum length of a name entered into the highscore list.
+const MAX_NAME_LENGTH: usize = 12;
+
+/// The maximum amount of entries listed per section of the run report.
+const MAX_REPORT_ENTRIES: usize = 4;
+/// The size of the section titles of the run report.
+const REPORT_TITLE_SIZE: f32 = 24.;
+/// The size of the entries of the run report.
+const REPORT_ENTRY_SIZE: f32 = 18.;
+
 /// The Menu that is shown over the game state when the game ends.
 pub struct GameOverMenu {
     /// The UI.
     ui: ui::UiElement<()>,
     /// The music player
     music_player: music::MusicPlayer,
+    /// The config a restarted game is started with.
+    restart: GameConfig,
+    /// The index of the entry this run made in the highscore list, if any.
+    entry: Option<usize>,
+    /// The name of that entry.
+    name: String,
+    /// Wether the player is currently typing that name.
+    editing: bool,
 }
 
 impl GameOverMenu {
-    /// Creates a new GameOverMenu displaying the passed score and adding it (if good enough) to the highscore list.
-    /// Also displays the highscore list and marks the newly achieved score (it it shows up).
-    pub fn new(ctx: &ggez::Context, wave: u32, score: u32) -> Result<Self, GameError> {
+    /// Creates a new GameOverMenu displaying the score of the passed entry and adding it (if good enough) to the highscore list.
+    /// Also displays the highscore list and marks the newly achieved score (it it shows up), as well as a report of the run.
+    /// If the entry made the list, the player is asked to enter a name for it.
+    /// Restarting keeps the difficulty and mutators of the passed config, or the whole config for custom runs.
+    pub fn new(
+        ctx: &ggez::Context,
+        entry: achievements::Highscore,
+        config: &GameConfig,
+    ) -> Result<Self, GameError> {
+        let score = entry.score;
+        let name = entry.name.clone();
+        let report =
+            create_run_report(ctx, &entry.stats.clone().unwrap_or_default(), &entry.spells);
+
         // load highscores
 
         let own_index = achievements::HIGHSCORES.with(|scores| {
             let highscores = &mut scores.borrow_mut();
             // if only a small amount of scores is recorded or the worst result from the list was beaten, insert the new result
-            if highscores.len() < 25 || score >= highscores.last().copied().unwrap_or_default().1 {
+            if highscores.len() < 25
+                || score
+                    >= highscores
+                        .last()
+                        .map(|entry| entry.score)
+                        .unwrap_or_default()
+            {
                 // set default index: at the end of the list
                 let mut index = highscores.len();
                 // see if there is an appropriate earlier index
-                for (i, &value) in highscores.iter().enumerate() {
-                    if score >= value.1 {
+                for (i, value) in highscores.iter().enumerate() {
+                    if score >= value.score {
                         index = i;
                         break;
                     }
                 }
                 // insert at appropriate index
-                highscores.insert(index, (wave, score));
+                highscores.insert(index, entry);
                 // if list has grown too much, cut last element
                 if highscores.len() > 25 {
                     highscores.pop();
@@ -45,58 +89,28 @@ impl GameOverMenu {
             }
         });
 
-        // create UI
-
-        let mut main_box = ui::containers::VerticalBox::new();
-        main_box.spacing = 25.;
-
-        // title
-
-        let game_over = graphics::Text::new(
-            graphics::TextFragment::new("Game Over!")
-                .color(graphics::Color::from_rgb_u32(PALETTE[8])),
-        )
-        .set_font("Retro")
-        .set_scale(54.)
-        .to_owned()
-        .to_element_builder(0, ctx)
-        .build();
-        main_box.add(game_over);
-
-        // horizontal box with own score left and highscores right
-
-        let mut score_box = ui::containers::HorizontalBox::new();
-        score_box.spacing = 35.;
-
         // own score + congratulatory message
 
-        let score_disp = graphics::Text::new(
-            graphics::TextFragment::new("Score\n")
-                .color(graphics::Color::from_rgb_u32(PALETTE[7]))
-                .scale(36.),
-        )
-        .add(
-            graphics::TextFragment::new(format!("{:>7}", score))
-                .color(graphics::Color::from_rgb_u32(PALETTE[6]))
-                .scale(32.),
-        )
-        .add(
-            // if new record, display that!
-            graphics::TextFragment::new(if own_index.unwrap_or(128) == 0 {
-                "\nNew Record!"
-            } else {
-                ""
-            })
-            .color(graphics::Color::from_rgb_u32(PALETTE[8]))
-            .scale(34.),
-        )
-        // monospace font for scores
-        .set_font("Retro_M")
-        .to_owned()
-        .to_element_builder(0, ctx)
-        .with_alignment(ui::Alignment::Center, ui::Alignment::Min)
-        .build();
-        score_box.add(score_disp);
+        let score_disp = Self::construct_score_display(ctx, score, own_index == Some(0));
+
+        // name of the new entry, if it made the list
+
+        let score_disp = if own_index.is_some() {
+            ui::containers::VerticalBox::new_spaced(15.)
+                .to_element_builder(0, ctx)
+                .with_child(score_disp)
+                .with_child(
+                    ui::containers::StackBox::new()
+                        .to_element_builder(NAME_CONTAINER_ID, ctx)
+                        .with_child(create_name_display(ctx, &name, true))
+                        .with_wrapper_layout(ui::Layout::default())
+                        .build(),
+                )
+                .with_alignment(ui::Alignment::Center, ui::Alignment::Min)
+                .build()
+        } else {
+            score_disp
+        };
 
         // list of 5 best scores so far
 
@@ -109,9 +123,9 @@ impl GameOverMenu {
         // add the first 5 (or less) scores as texts to the element
 
         achievements::HIGHSCORES.with(|scores| {
-            for (index, &(_, score)) in scores.borrow().iter().enumerate().take(5) {
+            for (index, entry) in scores.borrow().iter().enumerate().take(5) {
                 highscore_disp.add(
-                    graphics::TextFragment::new(format!("\n  {:02}.{:>5}", index + 1, score))
+                    graphics::TextFragment::new(format!("\n  {:02}.{:>5}", index + 1, entry.score))
                         .color(graphics::Color::from_rgb_u32(
                             // if own score shows up, change color to make it stand out
                             if index == own_index.unwrap_or(128) {
@@ -134,10 +148,219 @@ impl GameOverMenu {
             .with_alignment(ui::Alignment::Center, ui::Alignment::Min)
             .build();
 
-        score_box.add(highscore_disp);
+        let restart = if config.mode == RunMode::Custom {
+            config.clone()
+        } else {
+            config.mutators.iter().fold(
+                GameConfig::default().with_difficulty(config.difficulty),
+                |config, mutator| config.with_mutator(*mutator),
+            )
+        };
+
+        Ok(Self {
+            entry: own_index,
+            name,
+            editing: own_index.is_some(),
+            ..Self::construct(
+                ctx,
+                "Game Over!",
+                score_disp,
+                highscore_disp,
+                report,
+                restart,
+            )?
+        })
+    }
+
+    /// Creates a new GameOverMenu for the end of a daily challenge, displaying the passed score and adding it
+    /// (if good enough) to the daily highscores, as well as a report of the run. Restarting replays the same challenge.
+    pub fn new_daily(
+        ctx: &ggez::Context,
+        wave: u32,
+        score: u32,
+        stats: &RunStats,
+        spells: &[String],
+        config: &GameConfig,
+    ) -> Result<Self, GameError> {
+        let date = config
+            .daily
+            .as_ref()
+            .map(|daily| daily.date.clone())
+            .unwrap_or_default();
+
+        let own_index = achievements::DAILY_HIGHSCORES
+            .with(|scores| scores.borrow_mut().insert(&date, wave, score));
+
+        let score_disp = Self::construct_score_display(ctx, score, own_index == Some(0));
+
+        // list of the 5 best scores of the day
+
+        let mut highscore_disp = graphics::Text::new(
+            graphics::TextFragment::new(format!("Daily {}", date))
+                .color(graphics::Color::from_rgb_u32(PALETTE[7]))
+                .scale(36.),
+        );
+
+        achievements::DAILY_HIGHSCORES.with(|scores| {
+            for (index, entry) in scores.borrow().day(&date).enumerate().take(5) {
+                highscore_disp.add(
+                    graphics::TextFragment::new(format!("\n  {:02}.{:>5}", index + 1, entry.score))
+                        .color(graphics::Color::from_rgb_u32(
+                            // if own score shows up, change color to make it stand out
+                            if Some(index) == own_index {
+                                PALETTE[8]
+                            } else {
+                                PALETTE[6]
+                            },
+                        ))
+                        .scale(32.),
+                );
+            }
+        });
+
+        let highscore_disp = highscore_disp
+            .set_font("Retro_M")
+            .to_owned()
+            .to_element_builder(0, ctx)
+            .with_alignment(ui::Alignment::Center, ui::Alignment::Min)
+            .build();
+
+        Self::construct(
+            ctx,
+            "Game Over!",
+            score_disp,
+            highscore_disp,
+            create_run_report(ctx, stats, spells),
+            config.clone(),
+        )
+    }
+
+    /// Creates a new GameOverMenu for the end of a campaign, displaying the passed score and a report of the run.
+    /// If the campaign was won, the completion is recorded apart from the highscores of endless runs.
+    /// Restarting replays the campaign with the passed config.
+    pub fn new_campaign(
+        ctx: &ggez::Context,
+        campaign: &str,
+        won: bool,
+        score: u32,
+        stats: &RunStats,
+        spells: &[String],
+        config: &GameConfig,
+    ) -> Result<Self, GameError> {
+        // record the completion
+        let new_best = won
+            && achievements::CAMPAIGN_RECORDS
+                .with(|records| records.borrow_mut().record_completion(campaign, score));
+
+        let score_disp = Self::construct_score_display(ctx, score, new_best);
+
+        // completions and best score of this campaign
+
+        let record = achievements::CAMPAIGN_RECORDS
+            .with(|records| records.borrow().campaigns.get(campaign).copied())
+            .unwrap_or_default();
+
+        let record_disp = graphics::Text::new(
+            graphics::TextFragment::new(campaign)
+                .color(graphics::Color::from_rgb_u32(PALETTE[7]))
+                .scale(36.),
+        )
+        .add(
+            graphics::TextFragment::new(format!(
+                "\n  Completed{:>4}\n  Best{:>9}",
+                record.completions, record.best_score
+            ))
+            .color(graphics::Color::from_rgb_u32(PALETTE[6]))
+            .scale(32.),
+        )
+        .set_font("Retro_M")
+        .to_owned()
+        .to_element_builder(0, ctx)
+        .with_alignment(ui::Alignment::Center, ui::Alignment::Min)
+        .build();
+
+        Self::construct(
+            ctx,
+            if won { "Victory!" } else { "Game Over!" },
+            score_disp,
+            record_disp,
+            create_run_report(ctx, stats, spells),
+            GameConfig {
+                seed: None,
+                ..config.clone()
+            },
+        )
+    }
+
+    /// Creates the element displaying the score of the run, congratulating the player on a new record.
+    fn construct_score_display(
+        ctx: &ggez::Context,
+        score: u32,
+        new_record: bool,
+    ) -> ui::UiElement<()> {
+        graphics::Text::new(
+            graphics::TextFragment::new("Score\n")
+                .color(graphics::Color::from_rgb_u32(PALETTE[7]))
+                .scale(36.),
+        )
+        .add(
+            graphics::TextFragment::new(format!("{:>7}", score))
+                .color(graphics::Color::from_rgb_u32(PALETTE[6]))
+                .scale(32.),
+        )
+        .add(
+            // if new record, display that!
+            graphics::TextFragment::new(if new_record { "\nNew Record!" } else { "" })
+                .color(graphics::Color::from_rgb_u32(PALETTE[8]))
+                .scale(34.),
+        )
+        // monospace font for scores
+        .set_font("Retro_M")
+        .to_owned()
+        .to_element_builder(0, ctx)
+        .with_alignment(ui::Alignment::Center, ui::Alignment::Min)
+        .build()
+    }
+
+    /// Creates the menu from its title, the score display on the left, the record display on the right and the run report below.
+    fn construct(
+        ctx: &ggez::Context,
+        title: &str,
+        score_disp: ui::UiElement<()>,
+        record_disp: ui::UiElement<()>,
+        report: ui::UiElement<()>,
+        restart_config: GameConfig,
+    ) -> Result<Self, GameError> {
+        // create UI
+
+        let mut main_box = ui::containers::VerticalBox::new();
+        main_box.spacing = 25.;
+
+        // title
+
+        let game_over = graphics::Text::new(
+            graphics::TextFragment::new(title).color(graphics::Color::from_rgb_u32(PALETTE[8])),
+        )
+        .set_font("Retro")
+        .set_scale(54.)
+        .to_owned()
+        .to_element_builder(0, ctx)
+        .build();
+        main_box.add(game_over);
+
+        // horizontal box with own score left and records right
+
+        let mut score_box = ui::containers::HorizontalBox::new();
+        score_box.spacing = 35.;
+        score_box.add(score_disp);
+        score_box.add(record_disp);
 
         main_box.add(score_box.to_element(0, ctx));
 
+        // report of the run
+
+        main_box.add(report);
+
         // restart button
 
         let restart = graphics::Text::new(
@@ -185,6 +408,10 @@ impl GameOverMenu {
         Ok(Self {
             ui: main_box,
             music_player,
+            restart: restart_config,
+            entry: None,
+            name: String::new(),
+            editing: false,
         })
     }
 }
@@ -193,12 +420,50 @@ impl scene_manager::Scene for GameOverMenu {
     fn update(&mut self, ctx: &mut ggez::Context) -> Result<scene_manager::SceneSwitch, GameError> {
         let messages = self.ui.manage_messages(ctx, None);
 
+        // enter the name of the new highscore entry
+
+        if let Some(index) = self.entry {
+            let mut changed = false;
+            if self.editing {
+                changed = super::type_name(ctx, &mut self.name, MAX_NAME_LENGTH);
+                if ctx.keyboard.is_key_just_pressed(VirtualKeyCode::Return) {
+                    self.editing = false;
+                    changed = true;
+                }
+            } else if messages.contains(&ui::UiMessage::Triggered(NAME_ID)) {
+                self.editing = true;
+                changed = true;
+            }
+
+            if changed {
+                achievements::HIGHSCORES.with(|scores| {
+                    if let Some(entry) = scores.borrow_mut().get_mut(index) {
+                        entry.name = self.name.clone();
+                    }
+                });
+                if !self.name.is_empty() {
+                    crate::options::OPTIONS
+                        .with(|opt| opt.borrow_mut().player_name = self.name.clone());
+                }
+                self.ui.remove_elements(NAME_ID);
+                self.ui.add_element(
+                    NAME_CONTAINER_ID,
+                    create_name_display(ctx, &self.name, self.editing),
+                );
+            }
+
+            // the buttons would react to the keys typed
+            if self.editing {
+                return Ok(mooeye::scene_manager::SceneSwitch::None);
+            }
+        }
+
         // restart the game
 
         if messages.contains(&ui::UiMessage::Triggered(1)) {
             self.music_player.stop(ctx);
             return Ok(mooeye::scene_manager::SceneSwitch::replace(
-                super::game_state::GameState::new(ctx, super::game_state::GameConfig::default())?,
+                super::game_state::GameState::new(ctx, self.restart.clone())?,
                 2,
             ));
         }
@@ -228,3 +493,254 @@ impl scene_manager::Scene for GameOverMenu {
         Ok(())
     }
 }
+
+/// Creates the element displaying the name of a new highscore entry. While not editing, it can be clicked to edit the name again.
+fn create_name_display(ctx: &ggez::Context, name: &str, editing: bool) -> ui::UiElement<()> {
+    graphics::Text::new(
+        graphics::TextFragment::new("Name: ").color(graphics::Color::from_rgb_u32(PALETTE[7])),
+    )
+    .add(
+        graphics::TextFragment::new(if editing {
+            format!("{}_", name)
+        } else {
+            name.to_owned()
+        })
+        .color(graphics::Color::from_rgb_u32(PALETTE[8])),
+    )
+    .add(
+        graphics::TextFragment::new(if editing {
+            "\nPress Enter to confirm."
+        } else {
+            ""
+        })
+        .color(graphics::Color::from_rgb_u32(PALETTE[6]))
+        .scale(20.),
+    )
+    .set_font("Retro")
+    .set_scale(24.)
+    .to_owned()
+    .to_element_builder(NAME_ID, ctx)
+    .with_visuals(super::BUTTON_VIS)
+    .with_hover_visuals(super::BUTTON_HOVER_VIS)
+    .build()
+}
+
+/// Creates a report of the passed run, listing kills by enemy, the casts and damage of the passed equipped spells,
+/// gold earned and spent, buildings lost and city damage taken by enemy, as well as a chart of the gold earned per wave.
+fn create_run_report(
+    ctx: &ggez::Context,
+    stats: &RunStats,
+    spells: &[String],
+) -> ui::UiElement<()> {
+    let names = StatNames::load();
+
+    let mut kills = graphics::Text::default();
+    add_report_section(
+        &mut kills,
+        &format!("Kills ({})", stats.kills),
+        by_count(by_name(&stats.enemy_kills, |id| names.enemy(id))),
+    );
+
+    let mut spell_report = graphics::Text::default();
+    add_report_section(
+        &mut spell_report,
+        "Spells (casts / damage)",
+        spells
+            .iter()
+            .map(|spell| {
+                let count = |counters: &[u32]| {
+                    names
+                        .spell_id(spell)
+                        .and_then(|id| counters.get(id))
+                        .copied()
+                        .unwrap_or_default()
+                };
+                (
+                    spell.clone(),
+                    format!(
+                        "{} / {}",
+                        count(&stats.spell_casts),
+                        count(&stats.spell_damage)
+                    ),
+                )
+            })
+            .collect(),
+    );
+
+    let mut economy = graphics::Text::default();
+    add_report_section(
+        &mut economy,
+        "Gold",
+        vec![
+            ("Earned".to_owned(), stats.gold_earned.to_string()),
+            ("Spent".to_owned(), stats.gold_spent.to_string()),
+        ],
+    );
+    add_report_section(
+        &mut economy,
+        "Buildings lost",
+        by_count(by_name(&stats.buildings_lost, |index| {
+            names.building(index)
+        })),
+    );
+
+    let mut damage = graphics::Text::default();
+    add_report_section(
+        &mut damage,
+        "City damage",
+        by_count(by_name(&stats.city_damage, |id| names.enemy(id))),
+    );
+
+    let mut columns = ui::containers::HorizontalBox::new_spaced(30.);
+    for mut column in [kills, spell_report, economy, damage] {
+        columns.add(
+            column
+                .set_font("Retro")
+                .to_owned()
+                .to_element_builder(0, ctx)
+                .with_alignment(ui::Alignment::Min, ui::Alignment::Min)
+                .build(),
+        );
+    }
+
+    // gold earned per wave
+
+    let chart_title = graphics::Text::new(
+        graphics::TextFragment::new(if stats.wave_gold.is_empty() {
+            "Gold per wave: No wave cleared."
+        } else {
+            "Gold per wave"
+        })
+        .color(graphics::Color::from_rgb_u32(PALETTE[7]))
+        .scale(REPORT_TITLE_SIZE),
+    )
+    .set_font("Retro")
+    .to_owned()
+    .to_element(0, ctx);
+
+    let mut report = ui::containers::VerticalBox::new_spaced(10.)
+        .to_element_builder(0, ctx)
+        .with_child(columns.to_element(0, ctx))
+        .with_child(chart_title);
+    if !stats.wave_gold.is_empty() {
+        report = report.with_child(
+            BarChart::new(
+                stats.wave_gold.clone(),
+                graphics::Color::from_rgb_u32(PALETTE[8]),
+            )
+            .to_element_builder(0, ctx)
+            .with_visuals(ui::Visuals {
+                background: graphics::Color::from_rgb_u32(PALETTE[13]),
+                ..super::BUTTON_VIS
+            })
+            .with_size(ui::Size::Fixed(600.), ui::Size::Fixed(60.))
+            .with_tooltip(
+                graphics::Text::new(
+                    graphics::TextFragment::new(format!(
+                        "Most gold in a wave: {}",
+                        stats.wave_gold.iter().max().copied().unwrap_or_default()
+                    ))
+                    .color(graphics::Color::from_rgb_u32(PALETTE[6])),
+                )
+                .set_scale(24.)
+                .set_font("Retro")
+                .to_owned()
+                .to_element_builder(0, ctx)
+                .with_visuals(super::BUTTON_VIS)
+                .build(),
+            )
+            .build(),
+        );
+    }
+
+    report
+        .with_alignment(ui::Alignment::Center, ui::Alignment::Min)
+        .build()
+}
+
+/// Adds a section with the passed title and named values to the passed report text, listing at most [MAX_REPORT_ENTRIES] values.
+fn add_report_section(text: &mut graphics::Text, title: &str, entries: Vec<(String, String)>) {
+    if !text.fragments().is_empty() {
+        text.add(graphics::TextFragment::new("\n").scale(REPORT_ENTRY_SIZE));
+    }
+    text.add(
+        graphics::TextFragment::new(format!("{}\n", title))
+            .color(graphics::Color::from_rgb_u32(PALETTE[7]))
+            .scale(REPORT_TITLE_SIZE),
+    );
+    if entries.is_empty() {
+        text.add(
+            graphics::TextFragment::new("  None\n")
+                .color(graphics::Color::from_rgb_u32(PALETTE[6]))
+                .scale(REPORT_ENTRY_SIZE),
+        );
+    }
+    let more = entries.len().saturating_sub(MAX_REPORT_ENTRIES);
+    for (name, value) in entries.into_iter().take(MAX_REPORT_ENTRIES) {
+        text.add(
+            graphics::TextFragment::new(format!("  {}: {}\n", name, value))
+                .color(graphics::Color::from_rgb_u32(PALETTE[6]))
+                .scale(REPORT_ENTRY_SIZE),
+        );
+    }
+    if more > 0 {
+        text.add(
+            graphics::TextFragment::new(format!("  and {} more\n", more))
+                .color(graphics::Color::from_rgb_u32(PALETTE[7]))
+                .scale(REPORT_ENTRY_SIZE),
+        );
+    }
+}
+
+/// Returns the entries of the passed named counters, the largest first.
+fn by_count(counters: BTreeMap<String, u64>) -> Vec<(String, String)> {
+    let mut entries: Vec<_> = counters.into_iter().collect();
+    entries.sort_by(|(_, a), (_, b)| b.cmp(a));
+    entries
+        .into_iter()
+        .map(|(name, count)| (name, count.to_string()))
+        .collect()
+}
+
+/// A ui-element drawing a bar for each of its values from left to right, scaled relative to the largest value.
+struct BarChart {
+    /// The values displayed.
+    values: Vec<u32>,
+    /// The color of the bars.
+    color: graphics::Color,
+}
+
+impl BarChart {
+    /// Creates a new bar chart.
+    fn new(values: Vec<u32>, color: graphics::Color) -> Self {
+        Self { values, color }
+    }
+}
+
+impl<T: Copy + Eq + Hash> ui::UiContent<T> for BarChart {
+    fn draw_content(
+        &mut self,
+        _ctx: &mut ggez::Context,
+        canvas: &mut graphics::Canvas,
+        param: ui::UiDrawParam,
+    ) {
+        let max = self.values.iter().max().copied().unwrap_or_default().max(1) as f32;
+        let width = param.target.w / self.values.len().max(1) as f32;
+        // leave a gap between bars unless they are too thin
+        let gap = if width >= 4. { width * 0.2 } else { 0. };
+
+        for (index, &value) in self.values.iter().enumerate() {
+            let height = param.target.h * value as f32 / max;
+            let bar = graphics::Rect::new(
+                param.target.x + index as f32 * width + gap / 2.,
+                param.target.y + param.target.h - height,
+                width - gap,
+                height,
+            );
+            canvas.draw(
+                &graphics::Quad,
+                param.param.dest_rect(bar).color(self.color),
+            );
+        }
+    }
+}
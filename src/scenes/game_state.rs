@@ -1,15 +1,13 @@
 use crate::{music, options};
 use ggez::{glam::Vec2, graphics, GameError};
-use legion::{component, systems::CommandBuffer, Entity, IntoQuery, Resources, Schedule, World};
+use legion::Entity;
 use mooeye::ui as mui;
 use mooeye::{scene_manager, sprite};
-
-use std::time::Duration;
-
+use std::{cell::Cell, collections::HashSet, rc::Rc};
 mod game_message;
 pub use game_message::GameMessage;
+pub use game_message::MessageQueue;
 pub use game_message::MessageReceiver;
-pub use game_message::MessageSet;
 
 mod game_data;
 
@@ -22,26 +20,43 @@ pub use controller::Controller;
 pub use controller::Interactions;
 
 pub mod achievements;
+pub mod daily;
+pub mod difficulty;
+pub mod mutators;
+pub mod run_stats;
+pub mod statistics;
 pub mod tutorial;
 
 mod game_config;
-pub use game_config::GameConfig;
+pub use components::spell::spell_names;
+pub use director::{enemy_names, Campaign, CAMPAIGN_PATH};
+pub use game_config::{GameConfig, RunMode};
+
+mod simulation;
+pub use simulation::Simulation;
+
+pub mod replay;
+
+pub mod savegame;
 
 mod ui;
 
 pub const BOUNDARIES: graphics::Rect = graphics::Rect::new(0., 0., 600., 900.);
 
+/// The random number generator all gameplay randomness is drawn from.
+/// It is seeded from the [GameConfig], so the same seed and the same inputs always result in the same game.
+/// This is the generator behind [rand::rngs::StdRng], used directly so its state can be saved along with a run.
+pub type GameRng = rand_chacha::ChaCha12Rng;
+
 /// The main struct representing the current game state.
 /// This is the core scene rendering & updating gameplay.
 pub struct GameState {
-    /// The ECS worlds, containing all acting entities.
-    world: World,
-    /// The ECS resources, data that must be available to all systems and is not bound to certain entities.
-    resources: Resources,
+    /// The simulation core, containing the ECS world, resources and gameplay schedule.
+    simulation: Simulation,
     /// The controller from which player interaction can be read.
     controller: Controller,
-    /// The main gameplay schedule, producing and consuming actions
-    action_prod_schedule: Schedule,
+    /// Wether the inputs of this game are recorded or read from a replay.
+    replay: replay::ReplayMode,
     /// The in-game GUI.
     gui: mui::UiElement<GameMessage>,
     /// The player for the background music
@@ -52,19 +67,23 @@ pub struct GameState {
     tutorial: tutorial::TutorialManager,
     /// The offset of the initial camera during the fly-in
     camera_offset: (f32, f32),
+    /// The config this game was started with, kept to be saved alongside the game.
+    config: GameConfig,
+    /// Set by the in-game menu to request saving this game and returning to the main menu.
+    save_request: Rc<Cell<bool>>,
 }
 
 impl GameState {
-    /// Creates a new game state.
-    pub fn new(ctx: &ggez::Context, config: GameConfig) -> Result<Self, GameError> {
-        // --- WORLD CREATION ---
-
-        // Create world
-        let mut world = World::default();
-
-        // --- RESOURCE INITIALIZATION ---
+    /// Creates a new game state, recording the players inputs.
+    pub fn new(ctx: &ggez::Context, mut config: GameConfig) -> Result<Self, GameError> {
+        // fix seed and unlocked spells so the recording can reproduce this run
+        config.seed = Some(config.seed.unwrap_or_else(GameConfig::random_seed));
+        let recorded_config = GameConfig {
+            achievements_unlocked: config.achievements_unlocked.snapshot(),
+            ..config.clone()
+        };
 
-        let options = options::OPTIONS.with(|opt| *opt.borrow());
+        let options = options::OPTIONS.with(|opt| opt.borrow().clone());
         let tutorial = if options.tutorial {
             tutorial::TutorialManager::new()
         } else {
@@ -74,188 +93,105 @@ impl GameState {
             options::OPTIONS.with(|opt| opt.borrow_mut().tutorial = false);
         }
 
+        Self::construct(
+            ctx,
+            config,
+            tutorial,
+            replay::ReplayMode::Recording(replay::ReplayRecorder::new(recorded_config)),
+            None,
+        )
+    }
+
+    /// Creates a new game state that plays back the inputs of the passed replay.
+    pub fn from_replay(ctx: &ggez::Context, replay: replay::Replay) -> Result<Self, GameError> {
+        let config = GameConfig {
+            seed: Some(replay.seed),
+            ..replay.config.clone()
+        };
+        Self::construct(
+            ctx,
+            config,
+            tutorial::TutorialManager::new_empty(),
+            replay::ReplayMode::Playback(replay::ReplayPlayer::new(replay)),
+            None,
+        )
+    }
+
+    /// Creates a new game state that resumes a saved run.
+    pub fn from_save(ctx: &ggez::Context, save: savegame::SaveGame) -> Result<Self, GameError> {
+        Self::construct(
+            ctx,
+            save.config.clone(),
+            tutorial::TutorialManager::new_empty(),
+            replay::ReplayMode::Unrecorded,
+            Some(save),
+        )
+    }
+
+    /// Creates a new game state with the given input mode, resuming the save game if one is passed.
+    fn construct(
+        ctx: &ggez::Context,
+        config: GameConfig,
+        tutorial: tutorial::TutorialManager,
+        replay: replay::ReplayMode,
+        save: Option<savegame::SaveGame>,
+    ) -> Result<Self, GameError> {
+        // --- RESOURCE INITIALIZATION ---
+
+        let options = options::OPTIONS.with(|opt| opt.borrow().clone());
+
         let achievement_set =
-            achievements::AchievementSet::load(ctx, config.achievements_unlocked.clone());
+            achievements::AchievementSet::load(Some(ctx), config.achievements_unlocked.clone());
         let sprite_pool = sprite::SpritePool::new().with_folder(ctx, "/sprites", true);
         let audio_pool =
             components::audio::AudioPool::new(options).with_folder(ctx, "/audio", true);
 
-        let boundaries = BOUNDARIES;
-        let spell_pool = components::spell::init_spell_pool(&sprite_pool, &achievement_set);
-        let game_data = game_data::GameData::new(config.starting_gold, config.starting_city_health);
-        let director = director::Director::new(&sprite_pool, &config);
-
         let mut music_player = music::MusicPlayer::from_folder(ctx, "/audio/music/in_game");
         music_player.poll_options();
         music_player.next_song(ctx);
 
-        Self::initalize_environment(&boundaries, &sprite_pool, &mut world)?;
-
-        // Add player
+        // --- SIMULATION CREATION ---
 
-        let player = world.push((
-            components::Position::new(boundaries.w / 2., boundaries.h - 64.),
-            components::BoundaryCollision::new(true, false, false),
-            components::Control::new(config.base_speed),
-            components::Graphics::from(
-                sprite_pool.init_sprite("/sprites/mage2", Duration::from_secs_f32(0.25))?,
-            ),
-            components::SpellCaster::new(
-                components::spell::init_base_spells(&spell_pool, &sprite_pool, &config.base_spells),
-                config.base_slots,
-            ),
-        ));
-
-        let mut message_set = MessageSet::new();
-        // insert this to make sure the city health is displayed correctly
-        message_set.insert(mui::UiMessage::Extern(GameMessage::UpdateCityHealth(
-            game_data.city_health,
-        )));
-
-        // --- RESOURCE INSERTION ---
-
-        let mut resources = Resources::default();
-        resources.insert(player);
-        resources.insert(game_data);
-        resources.insert(message_set);
-        resources.insert(boundaries);
-        resources.insert(director);
-        resources.insert(spell_pool);
-        resources.insert(sprite_pool);
-        resources.insert(audio_pool);
+        let resumed = save.is_some();
+        let mut simulation = match save {
+            Some(save) => Simulation::from_save(save, sprite_pool, &achievement_set),
+            None => Simulation::new(&config, sprite_pool, &achievement_set),
+        }
+        .with_audio(audio_pool);
 
         // --- UI CREATION ---
 
         let mut gui = ui::game_ui::construct_game_ui(ctx, config.clone())?;
-        ui::wave_menu::sync_ui(ctx, &mut gui, &mut world, &mut resources);
+        // reopen the wave menu if the game was saved in it
+        if resumed && simulation.is_between_waves() {
+            ui::wave_menu::open_wave_menu(ctx, &mut gui, simulation.get_wave());
+        }
+        ui::wave_menu::sync_ui(
+            ctx,
+            &mut gui,
+            &mut simulation.world,
+            &mut simulation.resources,
+        );
 
-        // --- SYSTEM REGISTRY / UI CONSTRUCTION / CONTROLLER INITIALIZATION ---
+        // --- CONTROLLER INITIALIZATION ---
         Ok(Self {
-            world,
+            simulation,
             camera_offset: (config.initial_camera_offset, config.initial_camera_offset),
             gui,
             music_player,
-            action_prod_schedule: Schedule::builder()
-                // director
-                .add_system(director::direct_system())
-                // sytems that produce actions
-                .add_system(components::collision::collision_system())
-                .add_system(components::position::velocity_system())
-                .add_system(components::health::enemy_system())
-                .add_system(components::control::control_system())
-                .add_system(components::duration::manage_durations_system())
-                .add_system(components::health::destroy_by_health_system())
-                .flush()
-                // systems that consume (but may produce) actions
-                .add_system(components::spell::spell_casting_system())
-                .add_system(components::actions::handle_effects_system())
-                .flush()
-                // buildings
-                .add_system(components::buildings::destroy_buildings_system())
-                .add_system(components::buildings::create_buildings_system())
-                // systems that consume actions
-                .add_system(components::actions::resolve_executive_actions_system())
-                .add_system(components::graphics::handle_particles_system())
-                .add_system(components::audio::audio_enqueue_system())
-                .add_system(components::position::resolve_move_system())
-                .add_system(components::collision::boundary_collision_system())
-                .add_system(components::collision::resolve_immunities_system())
-                .add_system(components::health::resolve_damage_system())
-                .add_system(components::actions::apply_silence_system())
-                .add_system(game_data::resolve_gama_data_system())
-                .add_system(components::health::enemy_death_sprite_system())
-                .add_system(components::health::remove_entities_system())
-                .add_system(components::actions::clear_system())
-                .build(),
             achievements: achievement_set,
             tutorial,
-            resources,
-            controller: Controller::from_path("./data/keymap.toml").unwrap_or_default(),
+            controller: crate::storage::load(
+                crate::paths::save_path("keymap.toml"),
+                "key bindings",
+            )
+            .unwrap_or_default(),
+            replay,
+            config,
+            save_request: Rc::new(Cell::new(false)),
         })
     }
 
-    /// Initializes the environment by spawning house and brush sprites.
-    fn initalize_environment(
-        boundaries: &graphics::Rect,
-        sprite_pool: &sprite::SpritePool,
-        world: &mut World,
-    ) -> Result<(), GameError> {
-        // Create cobble sprites
-        for _i in 0..48 {
-            world.push((
-                components::Position::new(
-                    boundaries.w * rand::random::<f32>(),
-                    boundaries.h * (rand::random::<f32>() * 2. - 0.5),
-                ),
-                components::Graphics::from({
-                    let mut cobble =
-                        sprite_pool.init_sprite("/sprites/environment/cobble", Duration::ZERO)?;
-                    cobble.set_variant(rand::random::<u32>());
-                    cobble
-                }),
-            ));
-        }
-
-        let building_size = 4. * 32.;
-
-        // Add tree sprites
-        let mut positions = Vec::new();
-        for _i in 0..12 {
-            let rand_x = rand::random::<f32>() * 8. - 4.;
-            positions.push(components::Position::new(
-                (rand_x) * building_size + if rand_x > 0. { boundaries.w } else { 0. },
-                (rand::random::<f32>() * 0.7 - 0.2) * boundaries.h,
-            ));
-        }
-        positions.sort_by(|p1, p2| {
-            p1.y.partial_cmp(&p2.y)
-                .expect("[ERROR/Radish] Ordering of y-coordinates in brush init failed.")
-        });
-        for pos in positions {
-            world.push((
-                pos,
-                components::Graphics::from({
-                    let mut tree =
-                        sprite_pool.init_sprite("/sprites/environment/tree", Duration::ZERO)?;
-                    tree.set_variant(rand::random::<u32>());
-                    tree
-                }),
-            ));
-        }
-
-        // Add building sprites
-        for (x, y) in [
-            // left side
-            (-1.2, -1.8),
-            (-0.55, -0.6),
-            (-2.6, -0.65),
-            (-2.1, 0.5),
-            (-0.9, 0.67),
-            (-1.6, -0.55),
-            // right side
-            (0.8, -1.6),
-            (2.1, -1.2),
-            (0.7, -0.3),
-            (2.4, -0.5),
-            (0.8, 0.8),
-            (2.3, 0.6),
-        ] {
-            world.push((
-                components::Position::new(
-                    building_size * x + if x > 0. { boundaries.w } else { 0. },
-                    y * building_size + boundaries.h,
-                ),
-                components::Graphics::from({
-                    let mut build =
-                        sprite_pool.init_sprite("/sprites/environment/building", Duration::ZERO)?;
-                    build.set_variant(rand::random::<u32>());
-                    build
-                }),
-            ));
-        }
-        Ok(())
-    }
-
     /// A helper function that draw the background street.
     pub fn draw_background(
         boundaries: &graphics::Rect,
@@ -286,22 +222,6 @@ impl GameState {
                 .dest(Vec2::new((screen_w + boundaries.w) / 2. - 4., 0.)),
         );
     }
-
-    /// A helper function that ensures every entity in the world has a certain component subset
-    fn ensure_default_components(&mut self) {
-        // running buffer of added components
-        let mut buffer = CommandBuffer::new(&self.world);
-
-        // query for all elements not equipped with an ACTIONS module
-        for ent in <Entity>::query()
-            .filter(!component::<components::Actions>())
-            .iter(&self.world)
-        {
-            buffer.add_component(*ent, components::Actions::new());
-        }
-
-        buffer.flush(&mut self.world, &mut self.resources);
-    }
 }
 
 impl scene_manager::Scene for GameState {
@@ -310,25 +230,40 @@ impl scene_manager::Scene for GameState {
         // |                     Preparation                       |
         // +-------------------------------------------------------+
 
-        // create interaction struct and insert as resource
-        self.resources.insert(self.controller.get_interactions(ctx));
-
-        // make sure all entities have all default components
-        self.ensure_default_components();
-
-        // +-------------------------------------------------------+
-        // |                   Action Handling                     |
-        // +-------------------------------------------------------+
+        // save and quit if requested from the in-game menu
+        if self.save_request.get() {
+            match self.simulation.save(&self.config) {
+                Some(save) => {
+                    if let Err(err) =
+                        save.save_to_file(crate::paths::save_path(savegame::SAVEGAME_PATH))
+                    {
+                        println!("[ERROR/Radish] Could not save game: {}", err);
+                    }
+                }
+                None => println!("[ERROR/Radish] Could not save game."),
+            }
+            self.achievements.save();
+            self.music_player.stop(ctx);
+            return Ok(scene_manager::SceneSwitch::replace(
+                crate::scenes::main_menu::MainMenu::new(ctx)?,
+                1,
+            ));
+        }
 
-        // if a tutorial message is being displayed and we are not in the wave menu, pause the game
-        let act = if let Some(director) = self.resources.get_mut::<director::Director>() {
-            !self.tutorial.is_active() || director.is_between_waves()
-        } else {
-            false
+        let interactions = match &mut self.replay {
+            replay::ReplayMode::Recording(_) | replay::ReplayMode::Unrecorded => {
+                // if a tutorial message is being displayed and we are not in the wave menu, pause the game
+                if !self.tutorial.is_active() || self.simulation.is_between_waves() {
+                    Some(self.controller.get_interactions(ctx))
+                } else {
+                    None
+                }
+            }
+            replay::ReplayMode::Playback(player) => player.next_frame(),
         };
-        if act {
-            self.action_prod_schedule
-                .execute(&mut self.world, &mut self.resources);
+
+        if let Some(interactions) = &interactions {
+            self.simulation.step(interactions.clone());
         }
 
         // +-------------------------------------------------------+
@@ -338,28 +273,60 @@ impl scene_manager::Scene for GameState {
         let mut switch = scene_manager::SceneSwitch::None;
 
         // acquire messages
-        let total_messages = if let Some(mut message_set) = self.resources.get_mut::<MessageSet>() {
-            // if message set can be retrieved, drain it
-            self.gui
-                .update(ctx, message_set.clone())
-                .union(
-                    &message_set
-                        .drain()
-                        .collect::<std::collections::HashSet<mui::UiMessage<GameMessage>>>(),
-                )
-                .copied()
-                .collect()
-        } else {
-            self.gui.update(ctx, None)
-        };
+        // the GUI only needs to know which messages occured, but listeners receive every occurence in order
+        let mut total_messages = self.simulation.drain_messages();
+        let gui_messages = self
+            .gui
+            .update(ctx, total_messages.iter().copied().collect::<HashSet<_>>());
+        total_messages.extend(
+            gui_messages
+                .into_iter()
+                .filter(|message| !matches!(message, mui::UiMessage::Extern(_))),
+        );
+
+        // record or play back triggered UI elements
+        match &mut self.replay {
+            replay::ReplayMode::Recording(recorder) => recorder.record(
+                interactions.as_ref(),
+                total_messages
+                    .iter()
+                    .filter_map(|message| match message {
+                        mui::UiMessage::Triggered(id) => Some(*id),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            replay::ReplayMode::Playback(player) => {
+                // ignore any live clicks except for opening the menu
+                total_messages.retain(
+                    |message| !matches!(message, mui::UiMessage::Triggered(id) if *id != 1),
+                );
+                total_messages.extend(
+                    player
+                        .triggers()
+                        .iter()
+                        .filter(|&&id| id != 1)
+                        .map(|&id| mui::UiMessage::Triggered(id)),
+                );
+            }
+            replay::ReplayMode::Unrecorded => {}
+        }
 
         // handle wave menu
         ui::wave_menu::handle_wave_menu(
             &total_messages,
             &mut self.gui,
             ctx,
-            &mut self.world,
-            &mut self.resources,
+            &mut self.simulation.world,
+            &mut self.simulation.resources,
+        );
+
+        // handle boss health bars
+        ui::game_ui::handle_boss_bar(
+            &total_messages,
+            &mut self.gui,
+            ctx,
+            &self.simulation.resources,
         );
 
         // handle listeners
@@ -371,7 +338,15 @@ impl scene_manager::Scene for GameState {
         // Escape menu
         if total_messages.contains(&mui::UiMessage::Triggered(1)) {
             self.achievements.save();
-            switch = scene_manager::SceneSwitch::push(ui::in_game_menu::InGameMenu::new(ctx)?);
+            // replays can't be saved, as their inputs would be lost
+            let save_request = match self.replay {
+                replay::ReplayMode::Playback(_) => None,
+                _ => Some(self.save_request.clone()),
+            };
+            switch = scene_manager::SceneSwitch::push(ui::in_game_menu::InGameMenu::new(
+                ctx,
+                save_request,
+            )?);
         }
 
         if total_messages.contains(&mui::UiMessage::Triggered(tutorial::TUTORIAL_CLOSE)) {
@@ -382,18 +357,55 @@ impl scene_manager::Scene for GameState {
         // |                   Game Over Check                     |
         // +-------------------------------------------------------+
 
-        if let Some(game_data) = self.resources.get::<game_data::GameData>() {
-            if game_data.city_health <= 0 {
-                // stop music player
-                self.music_player.stop(ctx);
-                if let Some(director) = self.resources.get_mut::<director::Director>() {
+        if let Some(game_data) = self.simulation.resources.get::<game_data::GameData>() {
+            if let Some(director) = self.simulation.resources.get::<director::Director>() {
+                // replays don't enter the highscores and simply keep running
+                if (game_data.city_health <= 0 || director.is_victorious())
+                    && !matches!(self.replay, replay::ReplayMode::Playback(_))
+                {
+                    // stop music player
+                    self.music_player.stop(ctx);
                     // create the game over menu, replacing any other attempted scene switch
+                    // campaigns and daily challenges record their results apart from the highscores of endless runs
+                    let score = game_data.get_score() as u32;
                     switch = scene_manager::SceneSwitch::push(
-                        crate::scenes::game_over_menu::GameOverMenu::new(
-                            ctx,
-                            director.get_wave(),
-                            game_data.get_score() as u32,
-                        )?,
+                        match (director.get_campaign(), &self.config.daily) {
+                            (Some(campaign), _) => {
+                                crate::scenes::game_over_menu::GameOverMenu::new_campaign(
+                                    ctx,
+                                    &campaign.name,
+                                    director.is_victorious(),
+                                    score,
+                                    &self.simulation.get_stats(),
+                                    &self.simulation.get_equipped_spells(),
+                                    &self.config,
+                                )?
+                            }
+                            (None, Some(_)) => {
+                                crate::scenes::game_over_menu::GameOverMenu::new_daily(
+                                    ctx,
+                                    director.get_wave(),
+                                    score,
+                                    &self.simulation.get_stats(),
+                                    &self.simulation.get_equipped_spells(),
+                                    &self.config,
+                                )?
+                            }
+                            (None, None) => crate::scenes::game_over_menu::GameOverMenu::new(
+                                ctx,
+                                achievements::Highscore::new(
+                                    director.get_wave(),
+                                    score,
+                                    &self.config,
+                                )
+                                .with_details(
+                                    self.simulation.get_equipped_spells(),
+                                    game_data.buildings.get_built(),
+                                    self.simulation.get_stats(),
+                                ),
+                                &self.config,
+                            )?,
+                        },
                     );
                 }
             }
@@ -416,6 +428,7 @@ impl scene_manager::Scene for GameState {
         // Draw background
         Self::draw_background(
             &self
+                .simulation
                 .resources
                 .get::<graphics::Rect>()
                 .map(|r| *r)
@@ -426,13 +439,15 @@ impl scene_manager::Scene for GameState {
 
         // Draw world
 
+        let interpolation = self.simulation.get_interpolation();
         components::graphics::draw_sprites(
-            &mut self.world,
-            &mut self.resources,
+            &mut self.simulation.world,
+            &mut self.simulation.resources,
             ctx,
             &mut canvas,
             mouse_listen && !self.tutorial.is_active(),
             &mut self.camera_offset,
+            interpolation,
         )?;
 
         // Draw GUI
@@ -454,8 +469,21 @@ impl scene_manager::Scene for GameState {
 
         // Sounds
 
-        components::audio::audio_play_system(ctx, &mut self.resources)?;
+        components::audio::audio_play_system(ctx, &mut self.simulation.resources)?;
 
         Ok(())
     }
 }
+
+impl Drop for GameState {
+    fn drop(&mut self) {
+        // saved runs are recorded once they are finished, replays were recorded when they were played
+        let stats = self.simulation.get_stats();
+        if !self.save_request.get()
+            && !matches!(self.replay, replay::ReplayMode::Playback(_))
+            && stats.duration > 0.
+        {
+            achievements::STATISTICS.with(|statistics| statistics.borrow_mut().record_run(&stats));
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::{cell::RefCell, fs};
+use std::{cell::RefCell, collections::BTreeMap};
 
 use ggez::graphics;
 use mooeye::{ui, ui::UiContainer, ui::UiContent};
@@ -8,49 +8,109 @@ use crate::PALETTE;
 
 pub const ACHIEVEMENT_BOX: u32 = 100;
 
+/// The path of the file all achievements are defined in, relative to the data folder.
+pub const ACHIEVEMENT_LIST_PATH: &str = "achievement_list.toml";
+
 use super::{
-    game_message::{GameMessageFilter, MessageReceiver},
+    game_message::{GameMessagePredicate, MessageReceiver},
     GameMessage,
 };
 
 thread_local! {
     pub static ACHIEVEMENTS: RefCell<AchievementProgress> = RefCell::new(
-        toml::from_str(&fs::read_to_string("./data/achievements.toml")
-            .unwrap_or_else(|_| "".to_owned()))
+        crate::storage::load(crate::paths::save_path("achievements.toml"), "achievements")
+            .unwrap_or_default()
+    );
+
+    pub static HIGHSCORES: RefCell<Vec<Highscore>> = RefCell::new(
+        crate::storage::load::<ScoreList>(crate::paths::save_path("highscores.toml"), "highscores")
+            .unwrap_or_default().scores
+    );
+
+    pub static DAILY_HIGHSCORES: RefCell<DailyScoreList> = RefCell::new(
+        crate::storage::load(crate::paths::save_path("daily_highscores.toml"), "daily highscores")
+            .unwrap_or_default()
+    );
+
+    pub static CAMPAIGN_RECORDS: RefCell<CampaignRecords> = RefCell::new(
+        crate::storage::load(crate::paths::save_path("campaign_records.toml"), "campaign records")
+            .unwrap_or_default()
+    );
+
+    pub static STATISTICS: RefCell<super::statistics::LifetimeStats> = RefCell::new(
+        crate::storage::load(crate::paths::save_path(super::statistics::STATISTICS_PATH), "statistics")
             .unwrap_or_default()
     );
+}
+
+/// Loads achievements, highscores, campaign records and statistics from their save files, so problems reading them are reported right away.
+pub fn load_data() {
+    ACHIEVEMENTS.with(|_| {});
+    HIGHSCORES.with(|_| {});
+    DAILY_HIGHSCORES.with(|_| {});
+    CAMPAIGN_RECORDS.with(|_| {});
+    STATISTICS.with(|_| {});
+}
 
-    pub static HIGHSCORES: RefCell<Vec<(u32, u32)>> = RefCell::new(
-            toml::from_str::<ScoreList>(&fs::read_to_string("./data/highscores.toml")
-                .unwrap_or_else(|_| "".to_owned()))
-                .unwrap_or_default().scores
-    )
+/// Returns the best highscore of a regular run, if any. Quick advance is based on this score.
+pub fn best_endless_score() -> Option<Highscore> {
+    HIGHSCORES.with(|scores| {
+        scores
+            .borrow()
+            .iter()
+            .find(|entry| entry.mode == super::RunMode::Endless)
+            .cloned()
+    })
 }
 
 pub fn save_data_to_file() {
     crate::scenes::game_state::achievements::ACHIEVEMENTS.with(|ach| {
-        if std::fs::write(
-            "./data/achievements.toml",
-            toml::to_string(ach).unwrap_or_default(),
-        )
-        .is_err()
+        if let Err(e) =
+            crate::storage::save(crate::paths::save_path("achievements.toml"), &*ach.borrow())
         {
-            println!("[ERROR/Radish] Could not save achievements.");
+            println!("[ERROR/Radish] Could not save achievements: {}", e);
         };
     });
 
     // Save highscores
     crate::scenes::game_state::achievements::HIGHSCORES.with(|scores| {
-        if std::fs::write(
-            "./data/highscores.toml",
-            toml::to_string(&ScoreList {
+        if let Err(e) = crate::storage::save(
+            crate::paths::save_path("highscores.toml"),
+            &ScoreList {
                 scores: scores.take(),
-            })
-            .unwrap_or_default(),
-        )
-        .is_err()
-        {
-            println!("[ERROR/Radish] Could not save highscores.")
+            },
+        ) {
+            println!("[ERROR/Radish] Could not save highscores: {}", e)
+        }
+    });
+
+    // Save daily highscores
+    DAILY_HIGHSCORES.with(|scores| {
+        if let Err(e) = crate::storage::save(
+            crate::paths::save_path("daily_highscores.toml"),
+            &*scores.borrow(),
+        ) {
+            println!("[ERROR/Radish] Could not save daily highscores: {}", e)
+        }
+    });
+
+    // Save campaign records
+    CAMPAIGN_RECORDS.with(|records| {
+        if let Err(e) = crate::storage::save(
+            crate::paths::save_path("campaign_records.toml"),
+            &*records.borrow(),
+        ) {
+            println!("[ERROR/Radish] Could not save campaign records: {}", e)
+        }
+    });
+
+    // Save lifetime statistics
+    STATISTICS.with(|statistics| {
+        if let Err(e) = crate::storage::save(
+            crate::paths::save_path(super::statistics::STATISTICS_PATH),
+            &*statistics.borrow(),
+        ) {
+            println!("[ERROR/Radish] Could not save statistics: {}", e)
         }
     });
 }
@@ -59,35 +119,233 @@ pub fn save_data_to_file() {
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ScoreList {
     /// The scores
-    pub scores: Vec<(u32, u32)>,
+    pub scores: Vec<Highscore>,
+}
+
+impl crate::storage::SaveFile for ScoreList {
+    const VERSION: u32 = 1;
+
+    fn migrate(from: u32, contents: &mut toml::Table) -> Result<(), String> {
+        // scores used to be saved as (wave, score) pairs
+        if from == 0 {
+            if let Some(toml::Value::Array(scores)) = contents.get_mut("scores") {
+                for score in scores.iter_mut() {
+                    if let toml::Value::Array(pair) = score {
+                        let mut entry = toml::Table::new();
+                        entry.insert("wave".to_owned(), pair.first().cloned().unwrap_or(0.into()));
+                        entry.insert("score".to_owned(), pair.get(1).cloned().unwrap_or(0.into()));
+                        *score = toml::Value::Table(entry);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A single entry of the highscore list.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Highscore {
+    /// The wave reached.
+    pub wave: u32,
+    /// The score achieved, including the bonus of all mutators.
+    pub score: u32,
+    /// The name the player entered for this score.
+    #[serde(default)]
+    pub name: String,
+    /// The date the run ended on, formatted as YYYY-MM-DD. Empty for scores from before dates were recorded.
+    #[serde(default)]
+    pub date: String,
+    /// Wether the run was a regular or a custom one.
+    #[serde(default)]
+    pub mode: super::RunMode,
+    /// The mutators active during the run.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mutators: Vec<super::mutators::Mutator>,
+    /// The difficulty the run was played on.
+    #[serde(default)]
+    pub difficulty: super::difficulty::Difficulty,
+    /// The names of the spells equipped at the end of the run.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spells: Vec<String>,
+    /// The names and levels of all buildings constructed during the run.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub buildings: BTreeMap<String, u8>,
+    /// The seed, duration and kills of the run. Missing for scores from before they were recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stats: Option<super::run_stats::RunStats>,
+}
+
+impl Highscore {
+    /// Creates a new highscore entry for a run played with the passed config.
+    /// The entry is dated today and named after the name last entered into the highscore list.
+    pub fn new(wave: u32, score: u32, config: &super::GameConfig) -> Self {
+        Self {
+            wave,
+            score,
+            name: crate::options::OPTIONS.with(|opt| opt.borrow().player_name.clone()),
+            date: super::daily::current_date(),
+            mode: config.mode,
+            mutators: config.mutators.clone(),
+            difficulty: config.difficulty,
+            ..Default::default()
+        }
+    }
+
+    /// Records the spells equipped and buildings constructed at the end of the run as well as its statistics, and returns the entry builder-pattern style.
+    pub fn with_details(
+        mut self,
+        spells: Vec<String>,
+        buildings: BTreeMap<String, u8>,
+        stats: super::run_stats::RunStats,
+    ) -> Self {
+        self.spells = spells;
+        self.buildings = buildings;
+        self.stats = Some(stats);
+        self
+    }
+}
+
+/// The scores of daily challenges, kept apart from the highscores of regular runs.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct DailyScoreList {
+    /// The scores, ordered by date (newest first) and score (best first).
+    #[serde(default)]
+    pub scores: Vec<DailyScore>,
+}
+
+impl crate::storage::SaveFile for DailyScoreList {
+    const VERSION: u32 = 1;
+}
+
+/// The maximum amount of scores kept per daily challenge.
+const DAILY_SCORES_PER_DAY: usize = 10;
+/// The maximum amount of days scores are kept for.
+const DAILY_SCORE_DAYS: usize = 30;
+
+impl DailyScoreList {
+    /// Inserts a score into the list, if it is among the best scores of its day.
+    /// Returns the rank of the inserted score within its day.
+    pub fn insert(&mut self, date: &str, wave: u32, score: u32) -> Option<usize> {
+        let rank = self
+            .day(date)
+            .take_while(|entry| entry.score > score)
+            .count();
+        if rank >= DAILY_SCORES_PER_DAY {
+            return None;
+        }
+        // insert before the first worse score of the same day or the first score of an older day
+        let index = self
+            .scores
+            .iter()
+            .position(|entry| {
+                entry.date.as_str() < date || (entry.date == date && entry.score <= score)
+            })
+            .unwrap_or(self.scores.len());
+        self.scores.insert(
+            index,
+            DailyScore {
+                date: date.to_owned(),
+                wave,
+                score,
+            },
+        );
+
+        // forget scores that dropped off their day or belong to days long past
+        let mut days: Vec<String> = Vec::new();
+        let mut per_day = 0;
+        self.scores.retain(|entry| {
+            if days.last() != Some(&entry.date) {
+                days.push(entry.date.clone());
+                per_day = 0;
+            }
+            per_day += 1;
+            per_day <= DAILY_SCORES_PER_DAY && days.len() <= DAILY_SCORE_DAYS
+        });
+
+        Some(rank)
+    }
+
+    /// Returns the scores of the passed day, best first.
+    pub fn day<'a>(&'a self, date: &'a str) -> impl Iterator<Item = &'a DailyScore> {
+        self.scores.iter().filter(move |entry| entry.date == date)
+    }
+}
+
+/// A single score achieved in a daily challenge.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyScore {
+    /// The date of the daily challenge.
+    pub date: String,
+    /// The wave reached.
+    pub wave: u32,
+    /// The score achieved.
+    pub score: u32,
+}
+
+/// The completions of all campaigns, kept apart from the highscores of endless runs.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct CampaignRecords {
+    /// The records of all campaigns completed at least once, by campaign name.
+    #[serde(default)]
+    pub campaigns: BTreeMap<String, CampaignRecord>,
+}
+
+impl crate::storage::SaveFile for CampaignRecords {
+    const VERSION: u32 = 1;
+}
+
+impl CampaignRecords {
+    /// Records a completion of the passed campaign with the passed score.
+    /// Returns true if this is a new best score for that campaign.
+    pub fn record_completion(&mut self, campaign: &str, score: u32) -> bool {
+        let record = self.campaigns.entry(campaign.to_owned()).or_default();
+        record.completions += 1;
+        let new_best = record.completions == 1 || score > record.best_score;
+        record.best_score = record.best_score.max(score);
+        new_best
+    }
+}
+
+/// The record of a single campaign.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+pub struct CampaignRecord {
+    /// How often the campaign has been completed.
+    pub completions: u32,
+    /// The best score any completion of the campaign achieved.
+    pub best_score: u32,
 }
 
 #[derive(Clone, Debug)]
 /// A struct that represents a feat to achvieve in the game (by triggering a message matching a condition a set amount of times)
 pub struct Achievement {
+    /// The stable identifier of this achievement, used to save its progress.
+    id: String,
     name: String,
     description: String,
     progress: u32,
     target: u32,
     icon: Option<graphics::Image>,
-    model_message: (GameMessage, GameMessageFilter),
+    condition: GameMessagePredicate,
 }
 
 impl Achievement {
     pub fn new(
+        id: &str,
         name: &str,
         description: &str,
         icon: impl Into<Option<graphics::Image>>,
         target: u32,
-        model_message: (GameMessage, GameMessageFilter),
+        condition: GameMessagePredicate,
     ) -> Self {
         Self {
+            id: id.to_owned(),
             name: name.to_owned(),
             description: description.to_owned(),
             progress: 0,
             icon: icon.into(),
             target,
-            model_message,
+            condition,
         }
     }
 
@@ -98,7 +356,7 @@ impl Achievement {
     /// Checks a message and increases the internal progress counter if it triggers this achievement.
     /// Returns true if this completed the achievement.
     pub fn listen(&mut self, message: &GameMessage) -> bool {
-        if self.model_message.1.check(&self.model_message.0, message) {
+        if self.condition.matches(message) {
             self.progress += 1;
             self.progress == self.target
         } else {
@@ -214,127 +472,26 @@ pub struct AchievementSet {
 }
 
 impl AchievementSet {
-    pub fn load(ctx: &ggez::Context, source: AchievementProgressSource) -> Self {
-        // 3x kill counts (1, 50, 1000) + 1x kill basic 1000
-        // 2x elite kills (1, 50)
-        // 4x waves reached (2, 5, 50, 10x10)
-        // 2x special kills (ghost, guardian)
-        // 3x upgrades // 1x lose building
-
-        let mut list = vec![
-            Achievement::new(
-                "First Blood",
-                "Kill an enemy.",
-                graphics::Image::from_path(ctx, "/sprites/achievements/a01_16_16.png").ok(),
-                1,
-                (GameMessage::EnemyKilled(0), GameMessageFilter::Type),
-            ),
-            Achievement::new(
-                "To Dust",
-                "Kill 50 enemies.",
-                graphics::Image::from_path(ctx, "/sprites/achievements/a02_16_16.png").ok(),
-                50,
-                (GameMessage::EnemyKilled(0), GameMessageFilter::Type),
-            ),
-            Achievement::new(
-                "They were legion",
-                "Kill 1000 enemies.",
-                graphics::Image::from_path(ctx, "/sprites/achievements/a03_16_16.png").ok(),
-                1000,
-                (GameMessage::EnemyKilled(0), GameMessageFilter::Type),
-            ),
-            Achievement::new(
-                "Puttin' on the broom",
-                "Kill 1000 non-elite enemies.",
-                graphics::Image::from_path(ctx, "/sprites/achievements/a04_16_16.png").ok(),
-                1000,
-                (GameMessage::EnemyKilled(10), GameMessageFilter::Max),
-            ),
-            Achievement::new(
-                "Survivor",
-                "Reach wave 2.",
-                graphics::Image::from_path(ctx, "/sprites/achievements/a05_16_16.png").ok(),
-                1,
-                (GameMessage::NextWave(2), GameMessageFilter::Equality),
-            ),
-            Achievement::new(
-                "Can't touch this",
-                "Reach wave 5.",
-                graphics::Image::from_path(ctx, "/sprites/achievements/a06_16_16.png").ok(),
-                1,
-                (GameMessage::NextWave(5), GameMessageFilter::Equality),
-            ),
-            Achievement::new(
-                "One kick, a thousand times",
-                "Reach level 8, 8 times.",
-                graphics::Image::from_path(ctx, "/sprites/achievements/a07_16_16.png").ok(),
-                8,
-                (GameMessage::NextWave(8), GameMessageFilter::Equality),
-            ),
-            Achievement::new(
-                "Supreme",
-                "Reach level 24.",
-                graphics::Image::from_path(ctx, "/sprites/achievements/a08_16_16.png").ok(),
-                1,
-                (GameMessage::NextWave(24), GameMessageFilter::Equality),
-            ),
-            Achievement::new(
-                "Royal Blood",
-                "Kill an elite enemy.",
-                graphics::Image::from_path(ctx, "/sprites/achievements/a09_16_16.png").ok(),
-                1,
-                (GameMessage::EnemyKilled(10), GameMessageFilter::Min),
-            ),
-            Achievement::new(
-                "Party like it's 1789",
-                "Kill 50 elite enemies.",
-                graphics::Image::from_path(ctx, "/sprites/achievements/a10_16_16.png").ok(),
-                50,
-                (GameMessage::EnemyKilled(10), GameMessageFilter::Min),
-            ),
-            Achievement::new(
-                "Speed limit",
-                "Kill 50 bannermen.",
-                graphics::Image::from_path(ctx, "/sprites/achievements/a11_16_16.png").ok(),
-                50,
-                (GameMessage::EnemyKilled(11), GameMessageFilter::Equality),
-            ),
-            Achievement::new(
-                "Who you gonna call?",
-                "Kill 15 ghosts.",
-                graphics::Image::from_path(ctx, "/sprites/achievements/a12_16_16.png").ok(),
-                15,
-                (GameMessage::EnemyKilled(16), GameMessageFilter::Equality),
-            ),
-            Achievement::new(
-                "The Lives of Others",
-                "Upgrade your watchtower five times.",
-                graphics::Image::from_path(ctx, "/sprites/achievements/a13_16_16.png").ok(),
-                5,
-                (GameMessage::BuildingUp(0, 1), GameMessageFilter::Min),
-            ),
-            Achievement::new(
-                "Union fees",
-                "Upgrade your mage's guild five times.",
-                graphics::Image::from_path(ctx, "/sprites/achievements/a14_16_16.png").ok(),
-                5,
-                (GameMessage::BuildingUp(1, 1), GameMessageFilter::Min),
-            ),
-            Achievement::new(
-                "Power Overwhelming!",
-                "Upgrade you mana well five times.",
-                graphics::Image::from_path(ctx, "/sprites/achievements/a15_16_16.png").ok(),
-                5,
-                (GameMessage::BuildingUp(2, 1), GameMessageFilter::Min),
-            ),
+    pub fn load(ctx: Option<&ggez::Context>, source: AchievementProgressSource) -> Self {
+        let mut list: Vec<Achievement> = crate::data::from_path::<AchievementList>(
+            crate::paths::data_path(ACHIEVEMENT_LIST_PATH),
+        )
+        .map_err(|e| println!("[ERROR/Radish] Could not load achievements: {}", e))
+        .unwrap_or_default()
+        .achievement
+        .into_iter()
+        .map(|definition| {
             Achievement::new(
-                "Oops",
-                "Lose ten buildings.",
-                graphics::Image::from_path(ctx, "/sprites/achievements/a16_16_16.png").ok(),
-                10,
-                (GameMessage::BuildingDown(0, 0), GameMessageFilter::Type),
-            ),
-        ];
+                &definition.id,
+                &definition.name,
+                &definition.description,
+                ctx.zip(definition.icon.as_ref())
+                    .and_then(|(ctx, icon)| graphics::Image::from_path(ctx, icon).ok()),
+                definition.target,
+                definition.condition,
+            )
+        })
+        .collect();
 
         // load progress
 
@@ -344,29 +501,31 @@ impl AchievementSet {
                     achievement.progress = (percent * achievement.target as f32) as u32;
                 }
             }
-            AchievementProgressSource::Cache => ACHIEVEMENTS.with(|ach| {
-                for i in 0..ach.borrow().progress_vals.len().min(list.len()) {
-                    list[i].progress = ach.borrow().progress_vals[i];
-                }
-            }),
+            AchievementProgressSource::Cache => {
+                ACHIEVEMENTS.with(|ach| ach.borrow().apply_to(&mut list))
+            }
+            AchievementProgressSource::Snapshot(progress) => progress.apply_to(&mut list),
         }
 
         Self { list, source }
     }
 
+    /// Returns the achievement with the passed id, if it exists.
+    pub fn get(&self, id: &str) -> Option<&Achievement> {
+        self.list.iter().find(|achievement| achievement.id == id)
+    }
+
     pub fn save(&self) {
         match &self.source {
-            AchievementProgressSource::Cache => {
-                let progress = AchievementProgress {
-                    progress_vals: self
-                        .list
-                        .iter()
-                        .map(|achievement| achievement.progress)
-                        .collect(),
-                };
-                ACHIEVEMENTS.with(|ach| *ach.borrow_mut() = progress)
-            }
-            AchievementProgressSource::Percentage(_) => {}
+            AchievementProgressSource::Cache => ACHIEVEMENTS.with(|ach| {
+                // progress of achievements not currently defined is kept
+                let mut ach = ach.borrow_mut();
+                for achievement in self.list.iter() {
+                    ach.progress
+                        .insert(achievement.id.clone(), achievement.progress);
+                }
+            }),
+            AchievementProgressSource::Percentage(_) | AchievementProgressSource::Snapshot(_) => {}
         }
     }
 }
@@ -381,11 +540,96 @@ impl Drop for AchievementSet {
 pub enum AchievementProgressSource {
     Percentage(f32),
     Cache,
+    /// A fixed copy of the progress at some point. Progress made is not saved.
+    Snapshot(AchievementProgress),
 }
 
+impl AchievementProgressSource {
+    /// Returns a source that will always load the progress this source would load right now.
+    pub fn snapshot(&self) -> Self {
+        match self {
+            Self::Cache => Self::Snapshot(ACHIEVEMENTS.with(|ach| ach.borrow().clone())),
+            other => other.clone(),
+        }
+    }
+}
+
+/// The progress of all achievements, keyed by their ids.
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct AchievementProgress {
-    progress_vals: Vec<u32>,
+    progress: BTreeMap<String, u32>,
+}
+
+impl AchievementProgress {
+    /// Sets the progress of all passed achievements to the progress saved for their ids.
+    fn apply_to(&self, list: &mut [Achievement]) {
+        for achievement in list.iter_mut() {
+            achievement.progress = self.progress.get(&achievement.id).copied().unwrap_or(0);
+        }
+    }
+}
+
+/// The ids of all achievements in the order their progress was saved in before progress was keyed by id.
+const LEGACY_ORDER: [&str; 16] = [
+    "first_blood",
+    "to_dust",
+    "they_were_legion",
+    "puttin_on_the_broom",
+    "survivor",
+    "cant_touch_this",
+    "one_kick",
+    "supreme",
+    "royal_blood",
+    "party_like_1789",
+    "speed_limit",
+    "who_you_gonna_call",
+    "lives_of_others",
+    "union_fees",
+    "power_overwhelming",
+    "oops",
+];
+
+impl crate::storage::SaveFile for AchievementProgress {
+    const VERSION: u32 = 1;
+
+    fn migrate(from: u32, contents: &mut toml::Table) -> Result<(), String> {
+        // progress used to be saved as a positional list instead of keyed by id
+        if from == 0 {
+            if let Some(toml::Value::Array(values)) = contents.remove("progress_vals") {
+                let progress = contents
+                    .entry("progress")
+                    .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+                if let toml::Value::Table(progress) = progress {
+                    for (id, value) in LEGACY_ORDER.iter().zip(values) {
+                        progress.entry(id.to_string()).or_insert(value);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The definition of an achievement as loaded from the achievement list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AchievementDefinition {
+    /// The stable identifier of the achievement. Must never change, as progress is saved by it.
+    id: String,
+    name: String,
+    description: String,
+    /// The path of the icon displayed once the achievement is achieved.
+    #[serde(default)]
+    icon: Option<String>,
+    /// How often a matching message needs to be received to achieve the achievement.
+    target: u32,
+    /// The condition messages this achievement listens for need to satisfy.
+    condition: GameMessagePredicate,
+}
+
+/// A list of all achievements, as saved in the achievement list file.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct AchievementList {
+    achievement: Vec<AchievementDefinition>,
 }
 
 impl MessageReceiver for AchievementSet {
@@ -1,17 +1,23 @@
-use std::{fmt::Debug, sync::Arc, time::Duration};
+use std::time::Duration;
 
 use ggez::glam::Vec2;
 use legion::{system, systems::CommandBuffer, Entity, EntityStore, IntoQuery};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 use tinyvec::TinyVec;
 
-use super::super::controller::Interactions;
+use super::super::{controller::Interactions, GameRng};
 
-use super::{Enemy, Position};
+use super::{
+    duration::{secs, secs_option},
+    Enemy, EntityTemplate, Position, SpellOrigin,
+};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 /// This enum contains all possible ways for entities to affect the world around them.
 pub enum GameAction {
     /// No action will be taken - useful if an action transformation should delete certain actions.
+    #[default]
     None,
     /// Removes the entity from the world.
     Remove(RemoveSource),
@@ -19,14 +25,23 @@ pub enum GameAction {
     Move { delta: Vec2 },
 
     /// Reduces the value of the health component.
-    TakeDamage { dmg: i32 },
+    TakeDamage {
+        dmg: i32,
+        /// The spell this damage originates from, if any.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        spell: Option<u8>,
+    },
     /// Increases the value of the health component (but not above max).
     TakeHealing { heal: i32 },
     /// Damages the main city.
     TakeCityDamage { dmg: i32 },
+    /// Restores health of the main city.
+    RepairCity { amount: i32 },
     /// Increases the players available money (and score).
     GainGold { amount: i32 },
-    /// Makes the entity 'other' immune to collisions with this entity
+    /// Makes the entity 'other' immune to collisions with this entity.
+    /// As entities can not be referenced outside of a running world, this action is never saved.
+    #[serde(skip)]
     AddImmunity { other: legion::Entity },
     /// Adds a particle to the entities graphics struct
     AddParticle(super::graphics::Particle),
@@ -34,48 +49,52 @@ pub enum GameAction {
     PlaySound(String),
     /// Instructs the spell casting component to cast a certain spell
     CastSpell(usize),
-    /// Executes a closure that is supposed to spawn an entity into the world. TODO: Closure evil, somehow serialize this?
-    Spawn(SpawnerBox),
+    /// Spawns one or more entities from a template, relative to the position of this entity.
+    Spawn(Box<EntityTemplate>),
+    /// Spawns an entity from a template that flies towards one of the enemies closest to this entity.
+    SpawnAimed {
+        /// The template of the spawned entity. Its velocity is replaced by the aimed one.
+        template: Box<EntityTemplate>,
+        /// The speed the spawned entity flies at.
+        speed: f32,
+        /// The amount of closest enemies a target is randomly chosen from.
+        candidates: usize,
+    },
+    /// Pulls all moving entities within range towards this entity.
+    Pull {
+        /// The maximum distance of pulled entities.
+        range: f32,
+        /// The maximum distance entities are pulled each time this action is applied.
+        strength: f32,
+    },
     /// Silences the entity for a duration.
-    Silence(Duration),
+    Silence(#[serde(with = "secs")] Duration),
     /// Applies a (temporary or permanent) effect to the target
     ApplyEffect(Box<ActionEffect>),
 }
 
-/// A box that contains a spawner lambda-functor.
-/// This allows implementing debug here and then deriving it at [GameAction].
-#[derive(Clone)]
-pub struct SpawnerBox {
-    spawner: Box<fn(Entity, Position, &mut CommandBuffer)>,
-}
-
-impl Debug for SpawnerBox {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("SpawnerBox").finish()
-    }
-}
-
 impl GameAction {
     /// Helper function to create a [GameAction::Spawn] without having to use Box.
-    pub fn spawn(spawner: fn(Entity, Position, &mut CommandBuffer)) -> Self {
-        Self::Spawn(SpawnerBox {
-            spawner: Box::new(spawner),
-        })
-    }
-
-    /// Helper function to create a [GameAction::PlaySound] with a string slice
-    pub fn play_sound(path: &str) -> Self {
-        Self::PlaySound(path.to_owned())
+    pub fn spawn(template: EntityTemplate) -> Self {
+        Self::Spawn(Box::new(template))
     }
-}
 
-impl Default for GameAction {
-    fn default() -> Self {
-        Self::None
+    /// Marks damage dealt and entities spawned by this action as originating from the passed spell, unless they already have an origin.
+    pub fn set_origin(&mut self, origin: u8) {
+        match self {
+            Self::TakeDamage { spell, .. } => {
+                spell.get_or_insert(origin);
+            }
+            Self::Spawn(template) | Self::SpawnAimed { template, .. } => {
+                template.set_origin(origin);
+            }
+            Self::ApplyEffect(effect) => effect.set_origin(origin),
+            _ => {}
+        }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 /// An enum that is carried by remove actions to inform the remover of the source triggering the removal
 pub enum RemoveSource {
     /// This entity has to be removed because it has reached zero health
@@ -90,7 +109,7 @@ pub enum RemoveSource {
     BuildingCollision,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 /// A struct that can be applied to an entity and represents a temporary effect on that entity
 pub struct ActionEffect {
     /// what is affected by this effect
@@ -98,49 +117,19 @@ pub struct ActionEffect {
     /// the effect itself
     content: ActionEffectType,
     /// how long this effect lasts
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "secs_option")]
     duration: Option<Duration>,
     /// how long this effect has been alive
+    #[serde(default, skip_serializing_if = "Duration::is_zero", with = "secs")]
     alive_duration: Duration,
 }
 
 impl ActionEffect {
     /// Creates a new transformation effect, that affects a certain set of entities for an unlimited amount of time, transforming all actions applied to them as specified.
-    pub fn transform(target: ActionEffectTarget, transform: fn(&mut GameAction)) -> Self {
-        Self {
-            target,
-            content: ActionEffectType::Transform(ActionTransformer {
-                transform: Arc::new(transform),
-            }),
-            duration: None,
-            alive_duration: Duration::ZERO,
-        }
-    }
-
-    /// Creates a new reaction effect, triggering on received actions to possibly trigger other actions
-    pub fn react(target: ActionEffectTarget, reaction: fn(&GameAction) -> ActionContainer) -> Self {
-        Self {
-            target,
-            content: ActionEffectType::Reaction(Reaction {
-                react: Arc::new(reaction),
-            }),
-            duration: None,
-            alive_duration: Duration::ZERO,
-        }
-    }
-
-    /// Creates a new repetition effect, applying the passed action(s) to a set of entities repeatedly for an unlimited amount of time.
-    pub fn repeat(
-        target: ActionEffectTarget,
-        actions: impl Into<ActionContainer>,
-        interval: Duration,
-    ) -> Self {
+    pub fn transform(target: ActionEffectTarget, transform: ActionTransform) -> Self {
         Self {
             target,
-            content: ActionEffectType::Repeat {
-                actions: actions.into(),
-                interval,
-                activations: 0.,
-            },
+            content: ActionEffectType::Transform(transform),
             duration: None,
             alive_duration: Duration::ZERO,
         }
@@ -175,6 +164,19 @@ impl ActionEffect {
         self.duration = Some(duration);
         self
     }
+
+    /// Marks all actions contained in this effect as originating from the passed spell.
+    pub fn set_origin(&mut self, origin: u8) {
+        match &mut self.content {
+            ActionEffectType::Repeat { actions, .. }
+            | ActionEffectType::Once(actions)
+            | ActionEffectType::OnDeath(_, actions)
+            | ActionEffectType::Reaction(ActionReaction::OnDamage { actions, .. }) => {
+                actions.set_origin(origin);
+            }
+            _ => {}
+        }
+    }
 }
 
 impl Default for ActionEffect {
@@ -188,23 +190,30 @@ impl Default for ActionEffect {
     }
 }
 
+/// Helper function to skip saving unused counters.
+fn is_zero(value: &f32) -> bool {
+    *value == 0.
+}
+
 impl From<ActionEffect> for GameAction {
     fn from(value: ActionEffect) -> Self {
         GameAction::ApplyEffect(Box::new(value))
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 /// The different types of effects that can appear
 enum ActionEffectType {
     /// Transformation: Transforms effects applied to entities.
-    Transform(ActionTransformer),
+    Transform(ActionTransform),
     /// Reactions: Triggers certain actions when receiving certain actions.
-    Reaction(Reaction),
+    Reaction(ActionReaction),
     /// Repetition: Repeatedly applies actions to entities.
     Repeat {
         actions: ActionContainer,
+        #[serde(with = "secs")]
         interval: Duration,
+        #[serde(default, skip_serializing_if = "is_zero")]
         activations: f32,
     },
     /// One-time effect: Applies actions to entities once.
@@ -213,7 +222,7 @@ enum ActionEffectType {
     OnDeath(RemoveSource, ActionContainer),
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 /// An enum that describes what targets to distribute an effect or ActionModification to.
 pub struct ActionEffectTarget {
     /// If only entities that have an [super::Enemy] component are affected.
@@ -224,6 +233,7 @@ pub struct ActionEffectTarget {
     affect_self: bool,
     /// The max amount of entities that will be affected. None means an unlimited amount.
     /// These will be sorted by distance to source entity.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     limit: Option<usize>,
 }
 
@@ -253,51 +263,97 @@ impl ActionEffectTarget {
         self.enemies_only = val;
         self
     }
-
-    /// Modifies this target to also apply to the source component. Returns self builder pattern style.
-    pub fn with_affect_self(mut self, val: bool) -> Self {
-        self.affect_self = val;
-        self
-    }
-
-    /// Modifies this target to only hit entities within a certain range. Returns self builder pattern style.
-    pub fn with_range(mut self, range: f32) -> Self {
-        self.range = range;
-        self
-    }
-
-    /// Modifies this distributor to only hit entities a limited amount of entities (sorted by range). Returns self builder pattern style.
-    pub fn with_limit(mut self, limit: usize) -> Self {
-        self.limit = Some(limit);
-        self
-    }
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 /// A composite component of [ActionEffect] that transforms actions.
-struct ActionTransformer {
-    transform: Arc<fn(&mut GameAction)>,
+pub enum ActionTransform {
+    /// Scales movement along both axes.
+    ScaleMove { x: f32, y: f32 },
+    /// Replaces horizontal movement with a fixed speed in the current direction and scales vertical movement.
+    Dash { speed: f32, y: f32 },
+    /// Replaces all movement with a straight downward launch that is faster the faster the entity already moved.
+    Launch { boost: f32, factor: f32 },
+    /// Scales all damage taken of at least a minimum amount.
+    ScaleDamage { factor: f32, min: i32 },
+    /// Scales all healing taken.
+    ScaleHealing { factor: f32 },
 }
 
-impl Debug for ActionTransformer {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("ActionTransformer").finish()
+impl ActionTransform {
+    /// Transforms a single action.
+    fn apply(&self, action: &mut GameAction) {
+        match (self, action) {
+            (Self::ScaleMove { x, y }, GameAction::Move { delta }) => {
+                delta.x *= x;
+                delta.y *= y;
+            }
+            (Self::Dash { speed, y }, GameAction::Move { delta }) => {
+                delta.x = speed * delta.x.signum();
+                delta.y *= y;
+            }
+            (Self::Launch { boost, factor }, GameAction::Move { delta }) => {
+                *delta = Vec2::new(0., (delta.length() + boost) * factor);
+            }
+            (Self::ScaleDamage { factor, min }, GameAction::TakeDamage { dmg, .. })
+                if *dmg >= *min =>
+            {
+                *dmg = (*dmg as f32 * factor) as i32;
+            }
+            (Self::ScaleHealing { factor }, GameAction::TakeHealing { heal }) => {
+                *heal = (*heal as f32 * factor) as i32;
+            }
+            _ => {}
+        }
     }
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 /// A compositive component of [ActionEffect] that reacts to received actions.
-struct Reaction {
-    react: Arc<fn(&GameAction) -> ActionContainer>,
+pub enum ActionReaction {
+    /// Whenever the entity takes at least a minimum amount of damage, it receives the contained actions.
+    OnDamage { min: i32, actions: ActionContainer },
+    /// Whenever the entity takes at least a minimum amount of damage, a fraction of it is passed on to the targets after a delay.
+    ShareDamage {
+        min: i32,
+        fraction: f32,
+        target: ActionEffectTarget,
+        #[serde(with = "secs")]
+        delay: Duration,
+    },
 }
 
-impl Debug for Reaction {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Reaction").finish()
+impl ActionReaction {
+    /// Returns the actions triggered by receiving a single action.
+    fn react(&self, action: &GameAction) -> ActionContainer {
+        match (self, action) {
+            (Self::OnDamage { min, actions }, GameAction::TakeDamage { dmg, .. }) if dmg >= min => {
+                actions.clone()
+            }
+            (
+                Self::ShareDamage {
+                    min,
+                    fraction,
+                    target,
+                    delay,
+                },
+                GameAction::TakeDamage { dmg, spell },
+            ) if dmg >= min => ActionEffect::once(
+                *target,
+                GameAction::TakeDamage {
+                    dmg: (*dmg as f32 * fraction) as i32,
+                    spell: *spell,
+                },
+            )
+            .with_duration(*delay)
+            .into(),
+            _ => GameAction::None.into(),
+        }
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
 /// Object that can contain either a single or a vector of GameActions. Mostly used to abstract away boxes and vecs from the user.
 pub enum ActionContainer {
     /// Contains only a single GameAction.
@@ -324,19 +380,45 @@ impl From<Vec<GameAction>> for ActionContainer {
     }
 }
 
+impl ActionContainer {
+    /// Marks all contained actions as originating from the passed spell.
+    pub fn set_origin(&mut self, origin: u8) {
+        match self {
+            Self::ApplySingle(action) => action.set_origin(origin),
+            Self::ApplyMultiple(actions) => {
+                for action in actions {
+                    action.set_origin(origin);
+                }
+            }
+        }
+    }
+
+    /// Marks all contained actions as originating from the passed spell and returns self builder-pattern style.
+    pub fn with_origin(mut self, origin: Option<&SpellOrigin>) -> Self {
+        if let Some(origin) = origin {
+            self.set_origin(origin.0);
+        }
+        self
+    }
+}
+
 impl Default for ActionContainer {
     fn default() -> Self {
         Self::ApplySingle(GameAction::None)
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
 /// A component that handles an entities interaction with the world via an action queue
 pub struct Actions {
-    /// The actions to be performed on this entity.
+    /// The actions to be performed on this entity. Only filled during a tick and therefore never saved.
+    #[serde(skip)]
     action_queue: TinyVec<[GameAction; 4]>,
     /// The effects that currently apply to this entity.
+    #[serde(default, skip_serializing_if = "TinyVec::is_empty")]
     effects: TinyVec<[ActionEffect; 4]>,
     /// The remaining duration this entity is silenced for, making effects not trigger.
+    #[serde(default, skip_serializing_if = "Duration::is_zero", with = "secs")]
     silence: Duration,
 }
 
@@ -383,17 +465,17 @@ impl Actions {
     }
 
     /// Transforms all actions currently in this entities action queue.
-    fn transform(&mut self, transform: &fn(&mut GameAction)) {
+    fn transform(&mut self, transform: &ActionTransform) {
         for action in self.action_queue.iter_mut() {
-            transform(action);
+            transform.apply(action);
         }
     }
 
     // Reacts to all currently stored actions, potentially creating new actions
-    fn react(&mut self, reaction: &fn(&GameAction) -> ActionContainer) {
+    fn react(&mut self, reaction: &ActionReaction) {
         let mut to_add = Vec::new();
         for action in self.action_queue.iter() {
-            to_add.push(reaction(action));
+            to_add.push(reaction.react(action));
         }
         for cont in to_add {
             self.push_container(cont);
@@ -424,16 +506,79 @@ pub fn apply_silence(actions: &mut Actions) {
 }
 
 #[system(for_each)]
-/// System that handles all spawn and other actions by executing their closures.
+/// System that handles all spawn and other actions that affect the world beyond a single entity.
 pub fn resolve_executive_actions(
     ent: &Entity,
     actions: &Actions,
     pos: Option<&Position>,
+    origin: Option<&SpellOrigin>,
     cmd: &mut CommandBuffer,
+    #[resource] rng: &mut GameRng,
 ) {
+    let pos_src = pos.copied().unwrap_or_default();
     for action in actions.get_actions() {
-        if let GameAction::Spawn(spawner) = action {
-            (spawner.spawner)(*ent, pos.copied().unwrap_or_default(), cmd);
+        match action {
+            GameAction::Spawn(template) => match origin {
+                // entities spawned by spell entities originate from the same spell
+                Some(origin) => {
+                    let mut template = template.clone();
+                    template.set_origin(origin.0);
+                    template.spawn(Some(*ent), pos_src, cmd, rng);
+                }
+                None => template.spawn(Some(*ent), pos_src, cmd, rng),
+            },
+            GameAction::SpawnAimed {
+                template,
+                speed,
+                candidates,
+            } => {
+                // roll the target now, as the world is not available until the buffer is flushed
+                let roll = rng.gen::<usize>();
+                // choose the target and spawn once the world is available
+                let source = *ent;
+                let mut template = template.clone();
+                if let Some(origin) = origin {
+                    template.set_origin(origin.0);
+                }
+                let (speed, candidates) = (*speed, *candidates);
+                cmd.exec_mut(move |world, resources| {
+                    // remember all enemy positions, sorted by distance to source
+                    let mut pos_list: Vec<Position> = <(&Enemy, &Position)>::query()
+                        .iter(world)
+                        .map(|(_, pos_tar)| *pos_tar)
+                        .collect();
+                    pos_list.sort_by(|a, b| a.distance(pos_src).total_cmp(&b.distance(pos_src)));
+
+                    // get one of the closest enemies
+                    if let Some(&target) = pos_list.get(roll % pos_list.len().clamp(1, candidates))
+                    {
+                        let mut buffer = CommandBuffer::new(world);
+                        if let Some(mut rng) = resources.get_mut::<GameRng>() {
+                            template
+                                .clone()
+                                .with_velocity_vec((target - pos_src).clamp_length(speed, speed))
+                                .spawn(Some(source), pos_src, &mut buffer, &mut rng);
+                        }
+                        buffer.flush(world, resources);
+                    }
+                });
+            }
+            GameAction::Pull { range, strength } => {
+                let (range, strength) = (*range, *strength);
+                cmd.exec_mut(move |world, _| {
+                    // iterate over all close moving entities
+                    for (_, pos_tar, act_tar) in
+                        <(&super::Velocity, &Position, &mut Actions)>::query()
+                            .iter_mut(world)
+                            .filter(|(_, pos, _)| pos.distance(pos_src) < range)
+                    {
+                        act_tar.push(GameAction::Move {
+                            delta: (pos_src - *pos_tar).clamp_length_max(strength),
+                        })
+                    }
+                });
+            }
+            _ => {}
         }
     }
 }
@@ -443,6 +588,7 @@ pub fn resolve_executive_actions(
 #[write_component(Actions)]
 #[read_component(Enemy)]
 #[read_component(Position)]
+#[read_component(SpellOrigin)]
 pub fn handle_effects(world: &mut legion::world::SubWorld, #[resource] ix: &Interactions) {
     // compile a list of all transforms & applies affecting entities
     let mut transforms = Vec::new();
@@ -450,7 +596,9 @@ pub fn handle_effects(world: &mut legion::world::SubWorld, #[resource] ix: &Inte
     let mut applies = Vec::new();
 
     // iterate over all sources of effects
-    for (src_ent, src_pos, src_act) in <(Entity, &Position, &Actions)>::query().iter(world) {
+    for (src_ent, src_pos, src_act, src_origin) in
+        <(Entity, &Position, &Actions, Option<&SpellOrigin>)>::query().iter(world)
+    {
         // skip silenced entities
         if !src_act.silence.is_zero() {
             continue;
@@ -480,7 +628,7 @@ pub fn handle_effects(world: &mut legion::world::SubWorld, #[resource] ix: &Inte
             {
                 match &effect.content {
                     ActionEffectType::Transform(transform) => {
-                        transforms.push((*target, transform.transform.clone()));
+                        transforms.push((*target, *transform));
                     }
                     ActionEffectType::Repeat {
                         actions,
@@ -488,11 +636,11 @@ pub fn handle_effects(world: &mut legion::world::SubWorld, #[resource] ix: &Inte
                         activations,
                     } => {
                         for _i in 0..(activations.floor()) as usize {
-                            applies.push((*target, actions.clone()));
+                            applies.push((*target, actions.clone().with_origin(src_origin)));
                         }
                     }
                     ActionEffectType::Reaction(reaction) => {
-                        reactions.push((*target, reaction.react.clone()));
+                        reactions.push((*target, reaction.clone()));
                     }
                     ActionEffectType::Once(actions) => {
                         if effect
@@ -500,14 +648,14 @@ pub fn handle_effects(world: &mut legion::world::SubWorld, #[resource] ix: &Inte
                             .map(|d| d <= effect.alive_duration + ix.delta)
                             .unwrap_or(false)
                         {
-                            applies.push((*target, actions.clone()));
+                            applies.push((*target, actions.clone().with_origin(src_origin)));
                         }
                     }
                     ActionEffectType::OnDeath(reason, actions) => {
                         if src_act.action_queue.iter().any(
                             |act| matches!(act, &GameAction::Remove(source) if source == *reason),
                         ) {
-                            applies.push((*target, actions.clone()));
+                            applies.push((*target, actions.clone().with_origin(src_origin)));
                         }
                     }
                 }
@@ -519,7 +667,7 @@ pub fn handle_effects(world: &mut legion::world::SubWorld, #[resource] ix: &Inte
     for (target, reaction) in reactions {
         if let Ok(mut entry) = world.entry_mut(target) {
             if let Ok(actions) = entry.get_component_mut::<Actions>() {
-                actions.react(reaction.as_ref());
+                actions.react(&reaction);
             }
         }
     }
@@ -537,7 +685,7 @@ pub fn handle_effects(world: &mut legion::world::SubWorld, #[resource] ix: &Inte
     for (target, transform) in transforms {
         if let Ok(mut entry) = world.entry_mut(target) {
             if let Ok(actions) = entry.get_component_mut::<Actions>() {
-                actions.transform(transform.as_ref());
+                actions.transform(&transform);
             }
         }
     }
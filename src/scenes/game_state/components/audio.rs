@@ -88,6 +88,6 @@ impl AudioPool {
 
     /// Checks for changes in the options file to change music volume if neccessary.
     pub fn poll_options(&mut self) {
-        self.options = crate::options::OPTIONS.with(|opt| *opt.borrow());
+        self.options = crate::options::OPTIONS.with(|opt| opt.borrow().clone());
     }
 }
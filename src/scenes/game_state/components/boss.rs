@@ -0,0 +1,97 @@
+use legion::system;
+use serde::{Deserialize, Serialize};
+
+use super::super::game_message;
+
+use super::{
+    actions::{ActionContainer, GameAction, RemoveSource},
+    Actions, Health,
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+/// A phase of a boss fight, entered once the bosses health drops to a fraction of its maximum.
+pub struct BossPhase {
+    /// The fraction of the bosses maximum health at which this phase is entered.
+    pub threshold: f32,
+    /// The actions applied to the boss on entering this phase.
+    /// Effects added by earlier phases stay active, so bosses grow more dangerous with every phase.
+    pub actions: ActionContainer,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+/// A component marking an enemy as a boss, managing its attack phases.
+pub struct Boss {
+    /// The index of the boss within the list of bosses.
+    id: u8,
+    /// The phases of this boss, ordered by descending threshold.
+    phases: Vec<BossPhase>,
+    /// The amount of phases already entered.
+    #[serde(default)]
+    phase: usize,
+    /// The health reported to listeners on the last pass.
+    #[serde(skip)]
+    last_health: Option<i32>,
+}
+
+impl Boss {
+    /// Creates a new boss component with the passed phases.
+    pub fn new(id: u8, mut phases: Vec<BossPhase>) -> Self {
+        phases.sort_by(|a, b| b.threshold.total_cmp(&a.threshold));
+        Self {
+            id,
+            phases,
+            phase: 0,
+            last_health: None,
+        }
+    }
+
+    /// Returns the index of the boss within the list of bosses.
+    pub fn get_id(&self) -> u8 {
+        self.id
+    }
+}
+
+#[system(for_each)]
+/// A system that advances bosses through their phases and informs listeners of their health and removal.
+pub fn boss_phases(
+    boss: &mut Boss,
+    health: &Health,
+    actions: &mut Actions,
+    #[resource] messages: &mut game_message::MessageQueue,
+) {
+    // enter all phases whose threshold has been passed
+    let fraction = health.get_current_health() as f32 / health.get_max_health().max(1) as f32;
+    while boss
+        .phases
+        .get(boss.phase)
+        .is_some_and(|phase| fraction <= phase.threshold)
+    {
+        actions.push_container(boss.phases[boss.phase].actions.clone());
+        boss.phase += 1;
+    }
+
+    // report health changes
+    if boss.last_health != Some(health.get_current_health()) {
+        boss.last_health = Some(health.get_current_health());
+        messages.push(mooeye::ui::UiMessage::Extern(
+            game_message::GameMessage::BossHealth {
+                boss: boss.id,
+                health: health.get_current_health(),
+                max_health: health.get_max_health(),
+            },
+        ));
+    }
+
+    // report removal
+    if let Some(source) = actions.get_actions().iter().find_map(|act| match act {
+        GameAction::Remove(source) => Some(*source),
+        _ => None,
+    }) {
+        messages.push(mooeye::ui::UiMessage::Extern(
+            game_message::GameMessage::BossRemoved {
+                boss: boss.id,
+                killed: source == RemoveSource::HealthLoss,
+            },
+        ));
+    }
+}
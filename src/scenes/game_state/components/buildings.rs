@@ -1,136 +1,205 @@
-use std::time::Duration;
+use std::{collections::HashMap, sync::OnceLock, time::Duration};
 
 use crate::scenes::game_state::game_data;
+use ggez::winit::event::VirtualKeyCode;
 use legion::{system, systems::CommandBuffer};
+use serde::{Deserialize, Serialize};
 
-pub enum BuildingType {
-    Watchtower = 0,
-    Mageguild = 1,
-    Manawell = 2,
-}
+/// The path of the file all buildings are defined in, relative to the data folder.
+pub const BUILDING_LIST_PATH: &str = "buildings.toml";
 
-pub const BUILDING_MAX_LEVEL: usize = 4;
-pub const BUILDING_TYPES: usize = 3;
+/// The unlock flag raised by the mage's guild, required to purchase higher level spells.
+pub const GUILD_UNLOCK: &str = "guild";
 
 pub struct Building {
     building_type: usize,
 }
 
-/// A struct representing the current state of the three buildable buildings
+/// A struct representing the current level of all buildable buildings
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Buildings {
-    pub target: [u8; BUILDING_TYPES],
-    current: [u8; BUILDING_TYPES],
+    pub target: Vec<u8>,
+    /// The buildings currently present in the world. Saved so a loaded game can spawn its buildings again via [restore_buildings] without announcing them.
+    #[serde(default)]
+    current: Vec<u8>,
+    /// Wether constructing buildings is forbidden in this run.
+    #[serde(default)]
+    locked: bool,
 }
 
 impl Buildings {
     pub fn new() -> Self {
         Self {
-            target: [0; 3],
-            current: [0; 3],
+            target: vec![0; get_building_list().len()],
+            current: vec![0; get_building_list().len()],
+            locked: false,
         }
     }
+
+    /// Forbids constructing any buildings for the rest of the run.
+    pub fn lock(&mut self) {
+        self.locked = true;
+    }
+
+    /// Returns wether constructing buildings is forbidden.
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Returns the names and levels of all buildings constructed at least once.
+    pub fn get_built(&self) -> std::collections::BTreeMap<String, u8> {
+        get_building_list()
+            .iter()
+            .zip(self.target.iter())
+            .filter(|(_, &level)| level > 0)
+            .map(|(info, &level)| (info.name.clone(), level))
+            .collect()
+    }
+
+    /// Returns the combined effects of all currently constructed building levels.
+    pub fn bonuses(&self) -> BuildingBonuses {
+        get_building_list()
+            .iter()
+            .zip(self.target.iter())
+            .flat_map(|(info, &level)| info.levels.iter().take(level as usize))
+            .flat_map(|level| level.effects.iter())
+            .fold(BuildingBonuses::default(), |mut bonuses, effect| {
+                match effect {
+                    BuildingEffect::MoveSpeed(speed) => bonuses.move_speed += speed,
+                    BuildingEffect::SpellSlots(slots) => bonuses.spell_slots += slots,
+                    BuildingEffect::Unlock(flag) => {
+                        *bonuses.unlocks.entry(flag.clone()).or_default() += 1
+                    }
+                    BuildingEffect::Reroll => bonuses.reroll = true,
+                }
+                bonuses
+            })
+    }
 }
 
-#[derive(Debug, Clone)]
+/// An effect a building level grants while constructed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum BuildingEffect {
+    /// Increases the movement speed of the player by a fraction of their base speed.
+    MoveSpeed(f32),
+    /// Grants additional spell slots.
+    SpellSlots(usize),
+    /// Raises the level of an unlock flag by one.
+    Unlock(String),
+    /// Allows rerolling the approaching enemies in the wave menu.
+    Reroll,
+}
+
+/// The combined effects of all constructed buildings.
+#[derive(Debug, Clone, Default)]
+pub struct BuildingBonuses {
+    /// The fraction of the base speed added to the movement speed of the player.
+    pub move_speed: f32,
+    /// The amount of additional spell slots.
+    pub spell_slots: usize,
+    /// The levels of all raised unlock flags.
+    unlocks: HashMap<String, u8>,
+    /// Wether the approaching enemies can be rerolled.
+    pub reroll: bool,
+}
+
+impl BuildingBonuses {
+    /// Returns the level of the passed unlock flag.
+    pub fn unlock_level(&self, flag: &str) -> u8 {
+        self.unlocks.get(flag).copied().unwrap_or_default()
+    }
+}
+
+/// A single level of a building.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildingLevel {
+    /// The cost to upgrade the building to this level.
+    pub cost: u32,
+    /// The effects granted while the building has at least this level.
+    #[serde(default)]
+    pub effects: Vec<BuildingEffect>,
+}
+
+/// The definition of a building type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BuildingInfo {
-    pub level_costs: [u32; BUILDING_MAX_LEVEL],
-    pub name: &'static str,
-    pub description: &'static str,
-    sprite: &'static str,
+    pub name: String,
+    pub description: String,
+    /// The sprite of the building in the game world.
+    sprite: String,
+    /// The icon of the building in the wave menu.
+    pub icon: String,
+    /// The key that constructs or upgrades the building in the wave menu.
+    pub key: VirtualKeyCode,
+    /// All levels of the building, in order.
+    pub levels: Vec<BuildingLevel>,
+}
+
+impl BuildingInfo {
+    /// Returns the maximum level this building can be upgraded to.
+    pub fn max_level(&self) -> u8 {
+        self.levels.len() as u8
+    }
+
+    /// Returns the cost to upgrade this building from the passed level, if it can be upgraded.
+    pub fn upgrade_cost(&self, level: u8) -> Option<u32> {
+        self.levels.get(level as usize).map(|level| level.cost)
+    }
+}
+
+/// A list of all buildings, as saved in the building list file.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct BuildingList {
+    building: Vec<BuildingInfo>,
 }
 
-const BUILDING_INFO_LIST: [BuildingInfo; BUILDING_TYPES] = [
-    BuildingInfo {
-        level_costs: [100, 150, 200, 200],
-        name: "Watchtower",
-        description:
-            "Increases your movement speed. First level instead allows rerolling approaching enemies.",
-        sprite: "/sprites/environment/watchtower",
-    },
-    BuildingInfo {
-        level_costs: [100, 150, 200, 200],
-        name: "Mage's Guild",
-        description: "Allows you to purchase higher level spells.",
-        sprite: "/sprites/environment/mageguild",
-    },
-    BuildingInfo {
-        level_costs: [250, 300, 350, 400],
-        name: "Mana Well",
-        description: "Adds an additional spell slot per level.",
-        sprite: "/sprites/environment/manawell",
-    },
-];
-
-const NO_BUILDING: BuildingInfo = BuildingInfo {
-    level_costs: [0; BUILDING_MAX_LEVEL],
-    name: "No Building",
-    description: "Does nothing. Should not exist",
-    sprite: "",
-};
-
-pub fn get_building_info(building: usize) -> &'static BuildingInfo {
-    BUILDING_INFO_LIST.get(building).unwrap_or(&NO_BUILDING)
+static BUILDING_LIST: OnceLock<Vec<BuildingInfo>> = OnceLock::new();
+
+/// Returns all buildable buildings, loading them from the building list on first use.
+pub fn get_building_list() -> &'static [BuildingInfo] {
+    BUILDING_LIST.get_or_init(|| {
+        crate::data::from_path::<BuildingList>(crate::paths::data_path(BUILDING_LIST_PATH))
+            .map_err(|e| println!("[ERROR/Radish] Could not load buildings: {}", e))
+            .unwrap_or_default()
+            .building
+    })
 }
 
 /// A system that check wether the target for a building level fits the current level and spawns buildings and sends messages as needed.
 #[system]
 pub fn create_buildings(
     #[resource] data: &mut game_data::GameData,
-    #[resource] message_set: &mut super::super::game_message::MessageSet,
+    #[resource] message_set: &mut super::super::game_message::MessageQueue,
     #[resource] boundaries: &ggez::graphics::Rect,
     cmd: &mut CommandBuffer,
 ) {
     // if 'target' is not 'current', spawn the appropriate building and send a message
 
-    for (i, info) in BUILDING_INFO_LIST.iter().enumerate() {
+    let list = get_building_list();
+    data.buildings.target.resize(list.len(), 0);
+    data.buildings.current.resize(list.len(), 0);
+
+    for (i, info) in list.iter().enumerate() {
         match data.buildings.target[i].cmp(&data.buildings.current[i]) {
             std::cmp::Ordering::Greater => {
                 // if building not yet built => spawn it
                 if data.buildings.current[i] == 0 {
-                    cmd.push((
-                        super::Position::new(
-                            boundaries.w / data.buildings.target.len() as f32 / 2.
-                                + boundaries.w * i as f32 / data.buildings.target.len() as f32,
-                            boundaries.h + 32. + 8.,
-                        ),
-                        Building { building_type: i },
-                        super::Collision::new(4. * 32., 2. * 32., true, |e1, e2| {
-                            vec![
-                                (
-                                    e1,
-                                    super::actions::GameAction::Remove(
-                                        super::actions::RemoveSource::BuildingCollision,
-                                    ),
-                                ),
-                                (
-                                    e2,
-                                    super::actions::GameAction::Remove(
-                                        super::actions::RemoveSource::EnemyReachedBottom,
-                                    ),
-                                ),
-                            ]
-                        }),
-                        super::Graphics::new(info.sprite, Duration::from_secs_f32(0.3))
-                            .with_sprite_variant(2),
-                    ));
+                    cmd.push(building_entity(i, list.len(), info, boundaries));
                 }
                 // inform everyone
-                message_set.insert(mooeye::ui::UiMessage::Extern(
-                    super::super::game_message::GameMessage::BuildingUp(
-                        i,
-                        data.buildings.target[i],
-                    ),
+                message_set.push(mooeye::ui::UiMessage::Extern(
+                    super::super::game_message::GameMessage::BuildingUp {
+                        building: i,
+                        level: data.buildings.target[i],
+                    },
                 ));
                 // update current
                 data.buildings.current[i] = data.buildings.target[i];
             }
             std::cmp::Ordering::Less => {
-                // inform everyone of downlevel
-                message_set.insert(mooeye::ui::UiMessage::Extern(
-                    super::super::game_message::GameMessage::BuildingDown(
-                        i,
-                        data.buildings.target[i],
-                    ),
+                // inform everyone of the loss
+                message_set.push(mooeye::ui::UiMessage::Extern(
+                    super::super::game_message::GameMessage::BuildingLost { building: i },
                 ));
                 // update
                 data.buildings.current[i] = data.buildings.target[i];
@@ -140,6 +209,59 @@ pub fn create_buildings(
     }
 }
 
+/// Pushes the entities of all buildings present when a game was saved to the world.
+/// Unlike [create_buildings], no messages are sent, so resumed games don't construct their buildings again.
+pub fn restore_buildings(
+    world: &mut legion::World,
+    buildings: &Buildings,
+    boundaries: &ggez::graphics::Rect,
+) {
+    let list = get_building_list();
+    for (i, info) in list.iter().enumerate() {
+        if buildings.current.get(i).is_some_and(|&level| level > 0) {
+            world.push(building_entity(i, list.len(), info, boundaries));
+        }
+    }
+}
+
+/// Returns the components of the entity representing the building with the passed index in the world.
+fn building_entity(
+    index: usize,
+    count: usize,
+    info: &BuildingInfo,
+    boundaries: &ggez::graphics::Rect,
+) -> (super::Position, Building, super::Collision, super::Graphics) {
+    (
+        super::Position::new(
+            boundaries.w / count as f32 / 2. + boundaries.w * index as f32 / count as f32,
+            boundaries.h + 32. + 8.,
+        ),
+        Building {
+            building_type: index,
+        },
+        super::Collision::new(
+            4. * 32.,
+            2. * 32.,
+            true,
+            vec![
+                (
+                    super::collision::CollisionTarget::Own,
+                    super::actions::GameAction::Remove(
+                        super::actions::RemoveSource::BuildingCollision,
+                    ),
+                ),
+                (
+                    super::collision::CollisionTarget::Other,
+                    super::actions::GameAction::Remove(
+                        super::actions::RemoveSource::EnemyReachedBottom,
+                    ),
+                ),
+            ],
+        ),
+        super::Graphics::new(&info.sprite, Duration::from_secs_f32(0.3)).with_sprite_variant(2),
+    )
+}
+
 #[system(for_each)]
 pub fn destroy_buildings(
     #[resource] data: &mut game_data::GameData,
@@ -152,6 +274,8 @@ pub fn destroy_buildings(
             super::actions::GameAction::Remove(super::actions::RemoveSource::BuildingCollision)
         )
     }) {
-        data.buildings.target[building.building_type] = 0;
+        if let Some(target) = data.buildings.target.get_mut(building.building_type) {
+            *target = 0;
+        }
     }
 }
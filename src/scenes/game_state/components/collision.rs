@@ -1,13 +1,24 @@
 use ggez::{glam::Vec2, graphics::Rect};
 use legion::{system, Entity, EntityStore, IntoQuery};
+use serde::{Deserialize, Serialize};
 
 use super::actions::Actions;
 
-/// A custom type to remember a set of delayed actions.
-type ActionQueue = Vec<(legion::Entity, GameAction)>;
+/// A custom type to remember a set of actions to apply on collision.
+type ActionQueue = Vec<(CollisionTarget, GameAction)>;
 
-use super::{actions::GameAction, health::Enemy, Position};
+use super::{actions::GameAction, health::Enemy, Position, SpellOrigin};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// Describes which of two colliding entities an action is applied to.
+pub enum CollisionTarget {
+    /// The entity owning the collision component.
+    Own,
+    /// The entity collided with.
+    Other,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 /// A component that manages an entities collision box and collision handling.
 pub struct Collision {
     /// Width of this elements collider.
@@ -15,35 +26,42 @@ pub struct Collision {
     /// Height of this elements collider.
     h: f32,
 
-    /// A function that provides a number of actions and messages to execute on collision, based on the entities colliding.
-    collision_handler: Box<dyn Fn(Entity, Entity) -> ActionQueue + Send + Sync>,
+    /// The actions to execute on collision, each applied to either of the entities colliding.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    on_collision: ActionQueue,
     /// Makes this collider respond only to collision with entities that have the 'Enemy' component.
     enemies_only: bool,
+    /// Makes this collider pierce through entities, colliding with every entity only once.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pierce: bool,
 
     /// A list of all entities that cannot collide with this one.
+    /// Entities only exist within a running world, so this list is saved separately.
+    #[serde(skip)]
     immunity: Vec<Entity>,
 }
 
 impl Collision {
     /// Creates a new collision component.
-    pub fn new(
-        w: f32,
-        h: f32,
-        enemies_only: bool,
-        collision_handler: impl Fn(Entity, Entity) -> ActionQueue + Send + Sync + 'static,
-    ) -> Self {
+    pub fn new(w: f32, h: f32, enemies_only: bool, on_collision: ActionQueue) -> Self {
         Self {
             w,
             h,
-            collision_handler: Box::new(collision_handler),
+            on_collision,
             enemies_only,
+            pierce: false,
             immunity: Vec::new(),
         }
     }
 
     /// Creates a new collision component that does itself not apply actions or send messages (but can trigger collisions with other collision components).
     pub fn new_basic(w: f32, h: f32) -> Self {
-        Self::new(w, h, false, |_, _| ActionQueue::new())
+        Self::new(w, h, false, ActionQueue::new())
+    }
+
+    /// Returns all entities that cannot collide with this one.
+    pub fn get_immunity(&self) -> &[Entity] {
+        &self.immunity
     }
 
     /// Returns the collision bounds (x,y,w,h) of this component.
@@ -58,6 +76,7 @@ impl Collision {
     }
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 /// A component that manages wether this element respects the game boundaries.
 pub struct BoundaryCollision {
     /// Wether the element respects the left and right boundaries.
@@ -147,6 +166,7 @@ pub fn boundary_collision(
 #[read_component(Position)]
 #[read_component(Collision)]
 #[read_component(Enemy)]
+#[read_component(SpellOrigin)]
 #[write_component(Actions)]
 /// A system that manages collisions of entities with each other.
 pub fn collision(world: &mut legion::world::SubWorld) {
@@ -154,7 +174,9 @@ pub fn collision(world: &mut legion::world::SubWorld) {
     let mut total_actions: Vec<(Entity, GameAction)> = Vec::new();
 
     // Iterate over all pairs of possible colliders.
-    for (ent1, pos1, col1) in <(Entity, &Position, &Collision)>::query().iter(world) {
+    for (ent1, pos1, col1, origin1) in
+        <(Entity, &Position, &Collision, Option<&SpellOrigin>)>::query().iter(world)
+    {
         for (ent2, pos2, col2, enemy2) in
             <(Entity, &Position, &Collision, Option<&Enemy>)>::query().iter(world)
         {
@@ -164,8 +186,23 @@ pub fn collision(world: &mut legion::world::SubWorld) {
                 && !col1.immunity.contains(ent2)
                 && (!col1.enemies_only || enemy2.is_some())
             {
-                let n_actions = (col1.collision_handler)(*ent1, *ent2);
-                total_actions.extend(n_actions);
+                if col1.pierce {
+                    total_actions.push((*ent1, GameAction::AddImmunity { other: *ent2 }));
+                }
+                total_actions.extend(col1.on_collision.iter().map(|(target, action)| {
+                    let mut action = action.clone();
+                    // actions of spell entities originate from the same spell
+                    if let Some(origin) = origin1 {
+                        action.set_origin(origin.0);
+                    }
+                    (
+                        match target {
+                            CollisionTarget::Own => *ent1,
+                            CollisionTarget::Other => *ent2,
+                        },
+                        action,
+                    )
+                }));
             }
         }
     }
@@ -1,9 +1,11 @@
 use super::super::controller;
 use ggez::glam::Vec2;
 use legion::system;
+use serde::{Deserialize, Serialize};
 
 use super::Actions;
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
 /// A component that allows an entity to be controlled by a player.
 pub struct Control {
     /// The speed at which movement orders move this entity.
@@ -51,17 +53,10 @@ pub fn control(
 
     // Spell casting
 
-    if let Some(true) = ix.commands.get(&controller::Command::Spell0) {
-        actions.push(super::actions::GameAction::CastSpell(0));
-    }
-    if let Some(true) = ix.commands.get(&controller::Command::Spell1) {
-        actions.push(super::actions::GameAction::CastSpell(1));
-    }
-    if let Some(true) = ix.commands.get(&controller::Command::Spell2) {
-        actions.push(super::actions::GameAction::CastSpell(2));
-    }
-    if let Some(true) = ix.commands.get(&controller::Command::Spell3) {
-        actions.push(super::actions::GameAction::CastSpell(3));
+    for i in 0..4 {
+        if let Some(true) = ix.commands.get(&controller::Command::spell_from_int(i)) {
+            actions.push(super::actions::GameAction::CastSpell(i));
+        }
     }
 
     if let Some(gfx) = gfx {
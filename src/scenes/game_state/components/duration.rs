@@ -1,13 +1,17 @@
 use legion::system;
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 use super::super::controller;
 
 use super::{actions::GameAction, Actions};
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
 /// A component that keeps a life duration of an entity and removes it after a certain time.
 pub struct LifeDuration {
+    #[serde(with = "secs")]
     life_duration: Duration,
+    #[serde(with = "secs")]
     max_duration: Duration,
 }
 
@@ -39,3 +43,44 @@ pub fn manage_durations(
         actions.push(GameAction::Remove(super::actions::RemoveSource::TimedOut))
     }
 }
+
+/// Serializes durations as a floating point amount of seconds, to keep data files readable.
+/// Use via `#[serde(with = "secs")]`.
+pub mod secs {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(duration.as_secs_f64())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        Duration::try_from_secs_f64(f64::deserialize(deserializer)?)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Serializes optional durations as a floating point amount of seconds, to keep data files readable.
+/// Use via `#[serde(with = "secs_option")]`.
+pub mod secs_option {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(
+        duration: &Option<Duration>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match duration {
+            Some(duration) => serializer.serialize_some(&duration.as_secs_f64()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Duration>, D::Error> {
+        Option::<f64>::deserialize(deserializer)?
+            .map(|secs| Duration::try_from_secs_f64(secs).map_err(serde::de::Error::custom))
+            .transpose()
+    }
+}
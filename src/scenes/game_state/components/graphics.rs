@@ -8,20 +8,22 @@ use ggez::{
 use mooeye::sprite;
 
 use legion::{system, IntoQuery};
+use serde::{Deserialize, Serialize};
 use tinyvec::TinyVec;
 
 use crate::PALETTE;
 
-use super::{actions::GameAction, Health, Position, Velocity};
+use super::{actions::GameAction, Health, Position, PreviousPosition, Velocity};
 
 pub const PIXEL_SIZE: f32 = 4.;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 /// The graphics component of an entity, containing a sprite to be drawn to the screen and a container for multiple additional particles.
 pub struct Graphics {
     /// The main sprite to represent this object.
     sprite: SpriteWrapper,
     /// Container for particles added to this and managed by this object.
+    #[serde(default, skip_serializing_if = "TinyVec::is_empty")]
     particles: TinyVec<[Particle; 4]>,
 }
 
@@ -43,13 +45,27 @@ impl Graphics {
             SpriteWrapper::PreInit(_, pre_init) => {
                 pre_init.set_variant(variant);
             }
-            SpriteWrapper::Initialized(sprite) => {
+            SpriteWrapper::Initialized(_, sprite) => {
                 sprite.set_variant(variant);
             }
         }
         self
     }
 
+    /// Adds a particle that is displayed from the start and returns the graphics component builder-pattern style.
+    pub fn with_particle(mut self, particle: Particle) -> Self {
+        self.particles.push(particle);
+        self
+    }
+
+    /// Returns a copy of this graphics component that only contains the main sprite.
+    pub fn without_particles(&self) -> Self {
+        Self {
+            sprite: self.sprite.clone(),
+            particles: TinyVec::new(),
+        }
+    }
+
     /// Returns the objects size in the world, already multiplied by PIXEL_SIZE.
     pub fn get_size(&self) -> (f32, f32) {
         (
@@ -62,7 +78,7 @@ impl Graphics {
     pub fn get_sprite(&self) -> &sprite::Sprite {
         match &self.sprite {
             SpriteWrapper::PreInit(_, pre_init) => pre_init,
-            SpriteWrapper::Initialized(sprite) => sprite,
+            SpriteWrapper::Initialized(_, sprite) => sprite,
         }
     }
 
@@ -70,26 +86,69 @@ impl Graphics {
     pub fn get_sprite_mut(&mut self) -> &mut sprite::Sprite {
         match &mut self.sprite {
             SpriteWrapper::PreInit(_, pre_init) => pre_init,
-            SpriteWrapper::Initialized(sprite) => sprite,
+            SpriteWrapper::Initialized(_, sprite) => sprite,
         }
     }
 }
 
-impl From<sprite::Sprite> for Graphics {
-    fn from(value: sprite::Sprite) -> Self {
-        Self {
-            sprite: SpriteWrapper::Initialized(value),
-            particles: TinyVec::new(),
-        }
-    }
-}
-
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "SpriteData", into = "SpriteData")]
 /// A wrapper that represents a sprite as held in a graphics component.
 /// The sprite can either be initialized (as a basic sprite) or just be a path and a default sprite that need to later be initialized via a sprite pool.
+/// Both remember their path, so they can be saved and initialized again later.
 enum SpriteWrapper {
     PreInit(String, sprite::Sprite),
-    Initialized(sprite::Sprite),
+    Initialized(String, sprite::Sprite),
+}
+
+#[derive(Serialize, Deserialize)]
+/// The saved form of a [SpriteWrapper], containing everything needed to initialize the sprite again.
+struct SpriteData {
+    /// The path of the sprite.
+    path: String,
+    /// The time each frame of the sprite is shown.
+    #[serde(
+        default,
+        skip_serializing_if = "Duration::is_zero",
+        with = "super::duration::secs"
+    )]
+    frame_time: Duration,
+    /// The variant of the sprite.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    variant: u32,
+}
+
+/// Helper function to skip saving unused offsets and velocities.
+fn is_zero_vec(value: &Vec2) -> bool {
+    *value == Vec2::ZERO
+}
+
+/// Helper function to skip saving default sprite variants.
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+impl From<SpriteWrapper> for SpriteData {
+    fn from(value: SpriteWrapper) -> Self {
+        match value {
+            SpriteWrapper::PreInit(path, sprite) | SpriteWrapper::Initialized(path, sprite) => {
+                Self {
+                    path,
+                    frame_time: sprite.get_frame_time(),
+                    variant: sprite.get_variant(),
+                }
+            }
+        }
+    }
+}
+
+impl From<SpriteData> for SpriteWrapper {
+    fn from(value: SpriteData) -> Self {
+        let mut sprite = sprite::Sprite::default();
+        sprite.set_frame_time(value.frame_time);
+        sprite.set_variant(value.variant);
+        Self::PreInit(value.path, sprite)
+    }
 }
 
 impl SpriteWrapper {
@@ -100,24 +159,26 @@ impl SpriteWrapper {
         sprite_pool: &mut sprite::SpritePool,
     ) -> Result<&mut sprite::Sprite, ggez::GameError> {
         if let Self::PreInit(path, pre_init) = self {
-            let mut sprite = sprite_pool.init_sprite_lazy(ctx, path, pre_init.get_frame_time())?;
+            let mut sprite =
+                sprite_pool.init_sprite_lazy(ctx, path.as_str(), pre_init.get_frame_time())?;
             sprite.set_variant(pre_init.get_variant());
-            *self = Self::Initialized(sprite);
+            *self = Self::Initialized(std::mem::take(path), sprite);
         }
         Ok(match self {
             SpriteWrapper::PreInit(_, _) => panic!("Should have been initialized already."),
-            SpriteWrapper::Initialized(sprite) => sprite,
+            SpriteWrapper::Initialized(_, sprite) => sprite,
         })
     }
 }
 
 impl Default for SpriteWrapper {
     fn default() -> Self {
-        Self::Initialized(sprite::Sprite::default())
+        Self::Initialized(String::new(), sprite::Sprite::default())
     }
 }
 
 /// Draws all the sprites in the world to their respective positions on the canvas.
+/// Positions are interpolated between the last two simulation ticks by the passed factor.
 pub fn draw_sprites(
     world: &mut legion::World,
     resources: &mut legion::Resources,
@@ -125,6 +186,7 @@ pub fn draw_sprites(
     canvas: &mut Canvas,
     animate: bool,
     camera_offset: &mut (f32, f32),
+    interpolation: f32,
 ) -> Result<(), ggez::GameError> {
     // get boundaries for relative moving
     let boundaries = *resources
@@ -139,9 +201,20 @@ pub fn draw_sprites(
         .get_mut::<sprite::SpritePool>()
         .ok_or_else(|| ggez::GameError::CustomError("Could not unpack boundaries.".to_owned()))?;
 
-    for (pos, gfx, vel, health) in
-        <(&Position, &mut Graphics, Option<&Velocity>, Option<&Health>)>::query().iter_mut(world)
+    for (pos, prev, gfx, vel, health) in <(
+        &Position,
+        Option<&PreviousPosition>,
+        &mut Graphics,
+        Option<&Velocity>,
+        Option<&Health>,
+    )>::query()
+    .iter_mut(world)
     {
+        // interpolate between the last two ticks
+        let pos = &prev
+            .map(|prev| prev.0.lerp(*pos, interpolation))
+            .unwrap_or(*pos);
+
         // get sprite
         let sprite = gfx.sprite.init(ctx, &mut sprite_pool)?;
 
@@ -271,59 +344,27 @@ pub fn draw_sprites(
     Ok(())
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 /// A struct that represents a Particle that can be added to a graphics component to be displayed on top of the main sprite.
 pub struct Particle {
     /// The drawable to be displayed.
     sprite: SpriteWrapper,
     /// Relative position of the sprites center to the center of the main sprite.
+    #[serde(default, skip_serializing_if = "is_zero_vec")]
     rel_pos: Vec2,
     /// Velocity this particle moves at (in pixels/s).
+    #[serde(default, skip_serializing_if = "is_zero_vec")]
     vel: Vec2,
     /// The remaining duration of this particle. If None, it will stay indefinitely.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "super::duration::secs_option"
+    )]
     duration: Option<Duration>,
 }
 
 impl Particle {
-    /// Creates a new particle with the passed sprite, infinite duration and no velocity or offset.
-    pub fn new(path: impl AsRef<std::path::Path>, frame_time: Duration) -> Self {
-        Self {
-            sprite: SpriteWrapper::PreInit(path.as_ref().to_string_lossy().to_string(), {
-                let mut sprite = sprite::Sprite::default();
-                sprite.set_frame_time(frame_time);
-                sprite
-            }),
-            rel_pos: Vec2::ZERO,
-            vel: Vec2::ZERO,
-            duration: None,
-        }
-    }
-
-    /// Sets the variant of the underlying sprite and returns the particle builder-pattern style.
-    pub fn with_sprite_variant(mut self, variant: u32) -> Self {
-        match &mut self.sprite {
-            SpriteWrapper::PreInit(_, pre_init) => {
-                pre_init.set_variant(variant);
-            }
-            SpriteWrapper::Initialized(sprite) => {
-                sprite.set_variant(variant);
-            }
-        }
-        self
-    }
-
-    /// Sets the duration of this particle and returns it builder-pattern style.
-    pub fn with_duration(mut self, duration: Duration) -> Self {
-        self.duration = Some(duration);
-        self
-    }
-
-    /// Sets the relative position of this particle and returns it builder-pattern style.
-    pub fn with_relative_position(mut self, rel_x: f32, rel_y: f32) -> Self {
-        self.rel_pos = Vec2::new(rel_x, rel_y);
-        self
-    }
-
     #[allow(dead_code)]
     /// Sets the relative position of this particle and returns it builder-pattern style.
     pub fn with_relative_position_vec(mut self, rel_pos: Vec2) -> Self {
@@ -331,9 +372,9 @@ impl Particle {
         self
     }
 
-    /// Sets the velocity of this particle and returns it builder-pattern style.
-    pub fn with_velocity(mut self, dx: f32, dy: f32) -> Self {
-        self.vel = Vec2::new(dx, dy);
+    /// Moves the relative position of this particle by the passed offset and returns it builder-pattern style.
+    pub fn with_relative_position_offset(mut self, offset: Vec2) -> Self {
+        self.rel_pos += offset;
         self
     }
 
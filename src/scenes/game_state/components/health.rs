@@ -1,17 +1,22 @@
 use std::time::Duration;
 
 use legion::{system, systems::CommandBuffer, Entity};
+use serde::{Deserialize, Serialize};
 
 use super::super::game_message;
 
 use super::*;
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
 /// The Health component track wether a unit has a life bar and can take damage.
 pub struct Health {
     curr_health: i32,
     max_health: i32,
     snapshot_health: f32,
     snapshot_delay: Duration,
+    /// The spell that last damaged this unit, if any.
+    #[serde(skip)]
+    last_hit: Option<u8>,
 }
 
 impl Health {
@@ -22,6 +27,7 @@ impl Health {
             max_health: health,
             snapshot_health: health as f32,
             snapshot_delay: Duration::ZERO,
+            last_hit: None,
         }
     }
 
@@ -41,6 +47,7 @@ impl Health {
     }
 }
 
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 /// The Enemy struct is both a marker struct for many interactions and contains the damage an enemy deals to the main objective and the bounty it provides on kill.
 pub struct Enemy {
     /// The damage this enemy deals to the main objective if it reaches the finish line.
@@ -50,6 +57,7 @@ pub struct Enemy {
     /// An identifier to check for certain enemy kills.
     id: u8,
     /// Marks if an enemy is elite or not, giving it increased health and bounty.
+    #[serde(default)]
     elite: bool,
 }
 
@@ -63,6 +71,20 @@ impl Enemy {
             elite: false,
         }
     }
+
+    /// Promotes this enemy to an elite, scaling the damage it deals to the city and its bounty. Returns self builder-pattern style.
+    pub fn with_elite(mut self, damage_factor: i32, bounty_factor: i32) -> Self {
+        self.elite = true;
+        self.damage *= damage_factor;
+        self.bounty *= bounty_factor;
+        self
+    }
+
+    /// Scales the bounty of this enemy. Returns self builder-pattern style.
+    pub fn with_bounty_factor(mut self, factor: f32) -> Self {
+        self.bounty = (self.bounty as f32 * factor).round() as i32;
+        self
+    }
 }
 
 #[system(for_each)]
@@ -82,8 +104,9 @@ pub fn remove_entities(entity: &Entity, actions: &Actions, cmd: &mut CommandBuff
 pub fn destroy_by_health(
     health: &Health,
     enemy: Option<&Enemy>,
+    pos: Option<&Position>,
     actions: &mut Actions,
-    #[resource] messages: &mut game_message::MessageSet,
+    #[resource] messages: &mut game_message::MessageQueue,
 ) {
     if health.curr_health <= 0 {
         // in case of enemies
@@ -93,15 +116,17 @@ pub fn destroy_by_health(
                 amount: enemy.bounty,
             });
             // inform subscribers of kill
-            messages.insert(mooeye::ui::UiMessage::Extern(
-                game_message::GameMessage::EnemyKilled(enemy.id),
+            let pos = pos.copied().unwrap_or_default();
+            messages.push(mooeye::ui::UiMessage::Extern(
+                game_message::GameMessage::EnemyKilled {
+                    enemy: enemy.id,
+                    elite: enemy.elite,
+                    spell: health.last_hit,
+                    x: pos.x as i32,
+                    y: pos.y as i32,
+                    bounty: enemy.bounty,
+                },
             ));
-            // also inform that it was an elite
-            if enemy.elite {
-                messages.insert(mooeye::ui::UiMessage::Extern(
-                    game_message::GameMessage::EliteKilled,
-                ));
-            }
         }
 
         actions.push(actions::GameAction::Remove(
@@ -128,16 +153,19 @@ pub fn enemy_death_sprite(
     }) {
         let sprite = gfx.get_sprite();
 
+        // sprites that have never been drawn have no dimensions and therefore no animation to play
+        let duration = if sprite.get_dimensions().0 > 0. {
+            sprite.get_cycle_time() - sprite.get_frame_time()
+        } else {
+            std::time::Duration::ZERO
+        };
+
         cmd.push((
             *pos,
             vel.map(|v| Velocity::new((f32::EPSILON).copysign(v.get_dx()), 0.))
                 .unwrap_or(Velocity::new(0., 0.)),
-            LifeDuration::new(sprite.get_cycle_time() - sprite.get_frame_time()),
-            {
-                let mut death_sprite = sprite.clone();
-                death_sprite.set_variant(1);
-                Graphics::from(death_sprite)
-            },
+            LifeDuration::new(duration),
+            gfx.without_particles().with_sprite_variant(1),
         ));
     }
 }
@@ -146,8 +174,10 @@ pub fn enemy_death_sprite(
 /// Applies all [TakeDamage] actions to their respective entities.
 pub fn resolve_damage(
     health: &mut Health,
+    enemy: Option<&Enemy>,
     actions: &Actions,
     #[resource] ix: &super::super::controller::Interactions,
+    #[resource] messages: &mut game_message::MessageQueue,
 ) {
     // update snapshot health
     let health_float = health.curr_health as f32;
@@ -159,9 +189,22 @@ pub fn resolve_damage(
     }
 
     for action in actions.get_actions() {
-        if let actions::GameAction::TakeDamage { dmg } = action {
+        if let actions::GameAction::TakeDamage { dmg, spell } = action {
             health.curr_health -= *dmg;
             health.snapshot_delay = Duration::from_secs_f32(1.5);
+            if spell.is_some() {
+                health.last_hit = *spell;
+            }
+            // inform subscribers of damage dealt to enemies
+            if let Some(enemy) = enemy {
+                messages.push(mooeye::ui::UiMessage::Extern(
+                    game_message::GameMessage::DamageDealt {
+                        enemy: enemy.id,
+                        spell: *spell,
+                        amount: *dmg,
+                    },
+                ));
+            }
         } else if let actions::GameAction::TakeHealing { heal } = action {
             health.curr_health = (health.curr_health + *heal).min(health.max_health);
             health.snapshot_health = health.curr_health as f32;
@@ -176,6 +219,7 @@ pub fn enemy(
     pos: Option<&Position>,
     actions: &mut Actions,
     #[resource] boundaries: &ggez::graphics::Rect,
+    #[resource] messages: &mut game_message::MessageQueue,
 ) {
     // if enemy reaches the city border, damage the city and remove the enemy
     if match pos {
@@ -183,6 +227,13 @@ pub fn enemy(
         Some(pos) => pos.y >= boundaries.h,
     } {
         actions.push(actions::GameAction::TakeCityDamage { dmg: enemy.damage });
+        // inform subscribers of the damage
+        messages.push(mooeye::ui::UiMessage::Extern(
+            game_message::GameMessage::CityDamaged {
+                enemy: enemy.id,
+                amount: enemy.damage,
+            },
+        ));
         actions.push(actions::GameAction::Remove(
             actions::RemoveSource::EnemyReachedBottom,
         ));
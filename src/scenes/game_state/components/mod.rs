@@ -1,5 +1,6 @@
 pub mod position;
 pub use position::Position;
+pub use position::PreviousPosition;
 pub use position::Velocity;
 
 pub mod graphics;
@@ -21,6 +22,7 @@ pub use duration::LifeDuration;
 
 pub mod spell;
 pub use spell::SpellCaster;
+pub use spell::SpellOrigin;
 
 pub mod actions;
 pub use actions::Actions;
@@ -28,3 +30,9 @@ pub use actions::Actions;
 pub mod audio;
 
 pub mod buildings;
+
+pub mod boss;
+pub use boss::Boss;
+
+pub mod template;
+pub use template::EntityTemplate;
@@ -1,11 +1,16 @@
 use ggez::glam::Vec2;
 use legion::system;
+use serde::{Deserialize, Serialize};
 
 use super::Actions;
 
 pub type Position = Vec2;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+/// The position an entity had before the last simulation tick. Used to interpolate between ticks when drawing.
+pub struct PreviousPosition(pub Position);
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 /// A system that manages a fixed movement of an entity each second.
 pub struct Velocity {
     /// The amount of pixels this unit travels horizontally each second.
@@ -49,6 +54,12 @@ impl From<Velocity> for Vec2 {
     }
 }
 
+#[system(for_each)]
+/// Remembers the position of all entities before they move this tick.
+pub fn remember_positions(pos: &Position, prev: &mut PreviousPosition) {
+    prev.0 = *pos;
+}
+
 #[system(for_each)]
 /// Moves entities with the velocity component.
 pub fn velocity(
@@ -1,6 +1,7 @@
 use ggez::graphics;
 use legion::system;
 use mooeye::{ui, ui::UiContent};
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use tinyvec::TinyVec;
 
@@ -15,60 +16,56 @@ use super::{
     Actions,
 };
 
-/// Module containing constructor functions for all fire spells.
-mod fire;
-/// Module containing constructor functions for all ice and lightning spells.
-mod iceligthning;
-/// Module containing constructor functions for all air, void and arcane spells.
-mod misc;
+/// Module containing the data file definitions of spells.
+mod definition;
 
 /// The maximum amount of Spell Slots an entity can (by default) have.
 pub const MAX_SPELL_SLOTS: usize = 8;
 
+/// Creates the spell pool from all spell definitions in the spell folder.
 pub fn init_spell_pool(
     sprite_pool: &mooeye::sprite::SpritePool,
     achievements: &achievements::AchievementSet,
 ) -> SpellPool {
     (
         None,
-        vec![
-            SpellTemplate::new(fire::construct_fireball(sprite_pool), 50).purchased(),
-            SpellTemplate::new(fire::construct_scorch(sprite_pool), 90),
-            SpellTemplate::new(fire::construct_mortar(sprite_pool), 145).guild_condition(1),
-            SpellTemplate::new(fire::construct_flameorb(sprite_pool), 50).guild_condition(2),
-            SpellTemplate::new(fire::construct_conflagrate(sprite_pool), 150)
-                .guild_condition(3)
-                .achievement_condition(achievements.list.get(8), sprite_pool),
-            SpellTemplate::new(fire::construct_phoenix(sprite_pool), 200)
-                .guild_condition(4)
-                .achievement_condition(achievements.list.get(5), sprite_pool),
-            SpellTemplate::new(iceligthning::construct_ice_bomb(sprite_pool), 75).purchased(),
-            SpellTemplate::new(iceligthning::construct_shard(sprite_pool), 60).guild_condition(1),
-            SpellTemplate::new(iceligthning::construct_ice_lance(sprite_pool), 80)
-                .guild_condition(2)
-                .achievement_condition(achievements.list.get(9), sprite_pool),
-            SpellTemplate::new(iceligthning::construct_lightning_orb(sprite_pool), 90),
-            SpellTemplate::new(iceligthning::construct_overload(sprite_pool), 120)
-                .guild_condition(1),
-            SpellTemplate::new(iceligthning::construct_lightning_ball(sprite_pool), 145)
-                .guild_condition(2)
-                .achievement_condition(achievements.list.get(1), sprite_pool),
-            SpellTemplate::new(misc::construct_gale_force(sprite_pool), 120).guild_condition(3),
-            SpellTemplate::new(misc::construct_airburst(sprite_pool), 170)
-                .guild_condition(4)
-                .achievement_condition(achievements.list.get(10), sprite_pool),
-            SpellTemplate::new(misc::construct_mind_wipe(sprite_pool), 200).guild_condition(3),
-            SpellTemplate::new(misc::construct_blackhole(sprite_pool), 200)
-                .guild_condition(4)
-                .achievement_condition(achievements.list.get(11), sprite_pool),
-            SpellTemplate::new(misc::construct_arcane_blast(sprite_pool), 140).guild_condition(3),
-            SpellTemplate::new(misc::construct_arcane_missiles(sprite_pool), 150)
-                .guild_condition(4)
-                .achievement_condition(achievements.list.get(14), sprite_pool),
-        ],
+        crate::data::load_folder::<definition::SpellDefinition>(
+            crate::paths::data_path(definition::SPELL_FOLDER),
+            "spell",
+        )
+        .iter()
+        .enumerate()
+        .map(|(index, definition)| {
+            definition.to_template(sprite_pool, achievements, index as u8 + 1)
+        })
+        .collect(),
     )
 }
 
+/// Returns the names of all spells defined in the spell folder, ordered by their index minus one.
+pub fn spell_names() -> Vec<String> {
+    crate::data::load_folder::<definition::SpellDefinition>(
+        crate::paths::data_path(definition::SPELL_FOLDER),
+        "spell",
+    )
+    .into_iter()
+    .map(|definition| definition.name)
+    .collect()
+}
+
+/// Reports an error for every spell in the spell pool that blocks more spell slots than any caster can have, as it could never be cast.
+pub fn check_castable(spell_pool: &SpellPool) {
+    for template in &spell_pool.1 {
+        if template.spell.get_slot_cost() > MAX_SPELL_SLOTS {
+            println!(
+                "[ERROR/Radish] Spell {} blocks {} spell slots and can't be cast.",
+                template.spell.name,
+                template.spell.get_slot_cost()
+            );
+        }
+    }
+}
+
 pub fn init_base_spells(
     spell_pool: &SpellPool,
     sprite_pool: &mooeye::sprite::SpritePool,
@@ -86,6 +83,45 @@ pub fn init_base_spells(
         .collect()
 }
 
+/// Returns the index of the passed spell within the spell pool, as used by [init_base_spells].
+/// Returns 0 if the spell is not part of the pool.
+pub fn get_spell_index(spell_pool: &SpellPool, spell: &Spell) -> usize {
+    spell_pool
+        .1
+        .iter()
+        .position(|template| {
+            template.spell.name == spell.name && template.spell.description == spell.description
+        })
+        .map(|index| index + 1)
+        .unwrap_or_default()
+}
+
+/// Returns the levels of all spells in the spell pool, to be restored via [restore_spell_levels].
+pub fn get_spell_levels(spell_pool: &SpellPool) -> Vec<u32> {
+    spell_pool.1.iter().map(|template| template.level).collect()
+}
+
+/// Restores the levels of all spells in the spell pool.
+/// Also increases the costs of spells just like purchasing the spells in the wave menu would have.
+pub fn restore_spell_levels(spell_pool: &mut SpellPool, levels: &[u32]) {
+    let mut purchases = 0;
+    for (template, &level) in spell_pool.1.iter_mut().zip(levels) {
+        if level > 0 && template.level == 0 {
+            purchases += 1;
+        }
+        template.level = level;
+    }
+    for template in spell_pool.1.iter_mut() {
+        if template.cost > 0 {
+            template.cost += 20 * purchases;
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// A component marking entities spawned by a spell, so damage they deal can be attributed to it.
+pub struct SpellOrigin(pub u8);
+
 /// A component managing spell casting and spell slots.
 pub struct SpellCaster {
     /// The spell slots available to this caster.
@@ -130,14 +166,34 @@ impl SpellCaster {
 
     /// Adds a new spell slot to this entity.
     pub fn set_extra_slots(&mut self, slots: usize) {
+        // buildings may grant more slots than an entity can have
+        let slots = (slots + self.base_slots).min(MAX_SPELL_SLOTS.max(self.base_slots));
         // cutoff if smaller
-        self.spell_slots.truncate(slots + self.base_slots);
+        self.spell_slots.truncate(slots);
         // add if bigger
-        for _ in 0..(slots + self.base_slots) - self.spell_slots.len() {
+        for _ in 0..slots - self.spell_slots.len() {
             self.spell_slots.push(Default::default());
         }
     }
 
+    /// Returns the remaining and total blockage of all spell slots of this entity.
+    pub fn get_spell_slots(&self) -> &[(Duration, Duration)] {
+        &self.spell_slots
+    }
+
+    /// Replaces the spell slots of this entity, including their blockage, as when restoring a saved game, and returns it builder-pattern style.
+    /// Entities keep their slots if none are passed.
+    pub fn with_spell_slots(mut self, slots: &[(Duration, Duration)]) -> Self {
+        if !slots.is_empty() {
+            self.spell_slots = slots
+                .iter()
+                .copied()
+                .take(MAX_SPELL_SLOTS.max(self.base_slots))
+                .collect();
+        }
+        self
+    }
+
     /// Returns the amount of current spell slots on this entity.
     pub fn get_slots(&self) -> usize {
         self.spell_slots.len()
@@ -151,14 +207,9 @@ impl SpellCaster {
             .count()
     }
 
-    /// Returns wether this entity has less spell slots than the maximum.
-    pub fn can_add(&self) -> bool {
-        self.spell_slots.len() < MAX_SPELL_SLOTS
-    }
-
     /// Attempts to cast a spell by checking wether the required slots are available and then blocking them.
-    /// Returns a set of actions to be added to the caster (in the [spell_casting] system).
-    fn attempt_cast(&mut self, index: usize) -> ActionContainer {
+    /// Returns the identifier of the spell and a set of actions to be added to the caster (in the [spell_casting] system), if the spell was cast.
+    fn attempt_cast(&mut self, index: usize) -> Option<(u8, ActionContainer)> {
         if let Some(spell) = self.spells.get(index) {
             if self.get_free_slots() >= spell.spell_slots.len() {
                 let mut ind = 0;
@@ -169,23 +220,30 @@ impl SpellCaster {
                         ind += 1;
                     }
                 }
-                return if let Some(sound) = &spell.sound {
-                    match spell.spell_.clone() {
-                        ActionContainer::ApplySingle(a) => ActionContainer::ApplyMultiple(vec![
-                            a,
-                            GameAction::PlaySound(sound.clone()),
-                        ]),
-                        ActionContainer::ApplyMultiple(mut vec) => {
-                            vec.push(GameAction::PlaySound(sound.clone()));
-                            ActionContainer::ApplyMultiple(vec)
+                let mut actions = spell.spell_.clone();
+                actions.set_origin(spell.id);
+                return Some((
+                    spell.id,
+                    if let Some(sound) = &spell.sound {
+                        match actions {
+                            ActionContainer::ApplySingle(a) => {
+                                ActionContainer::ApplyMultiple(vec![
+                                    a,
+                                    GameAction::PlaySound(sound.clone()),
+                                ])
+                            }
+                            ActionContainer::ApplyMultiple(mut vec) => {
+                                vec.push(GameAction::PlaySound(sound.clone()));
+                                ActionContainer::ApplyMultiple(vec)
+                            }
                         }
-                    }
-                } else {
-                    spell.spell_.clone()
-                };
+                    } else {
+                        actions
+                    },
+                ));
             }
         }
-        ActionContainer::ApplySingle(GameAction::None)
+        None
     }
 }
 
@@ -194,7 +252,7 @@ impl SpellCaster {
 pub fn spell_casting(
     caster: &mut SpellCaster,
     actions: &mut Actions,
-    #[resource] messages: &mut game_message::MessageSet,
+    #[resource] messages: &mut game_message::MessageQueue,
     #[resource] ix: &controller::Interactions,
 ) {
     // reduce cooldowns
@@ -208,7 +266,7 @@ pub fn spell_casting(
 
     for (i, slot) in caster.spell_slots.iter().enumerate() {
         if !slot.1.is_zero() {
-            messages.insert(ui::UiMessage::Extern(
+            messages.push(ui::UiMessage::Extern(
                 game_message::GameMessage::UpdateSpellSlots(
                     i,
                     (slot.0.as_secs_f32() / slot.1.as_secs_f32() * 32.) as u8,
@@ -224,7 +282,13 @@ pub fn spell_casting(
             .commands
             .contains_key(&controller::Command::spell_from_int(i))
         {
-            actions.push_container(caster.attempt_cast(i))
+            if let Some((spell, cast)) = caster.attempt_cast(i) {
+                actions.push_container(cast);
+                // inform subscribers of the cast
+                messages.push(ui::UiMessage::Extern(
+                    game_message::GameMessage::SpellCast { spell },
+                ));
+            }
         }
     }
 }
@@ -348,11 +412,17 @@ impl SpellTemplate {
             } else {
                 ().to_element(0, ctx)
             })
-            .with_child(if buildings.target[1] >= self.guild_condition {
-                ().to_element(0, ctx)
-            } else {
-                guild
-            })
+            .with_child(
+                if buildings
+                    .bonuses()
+                    .unlock_level(super::buildings::GUILD_UNLOCK)
+                    >= self.guild_condition
+                {
+                    ().to_element(0, ctx)
+                } else {
+                    guild
+                },
+            )
             .with_child(
                 crate::scenes::game_state::ui::game_ui::Covering::new(
                     graphics::Color {
@@ -371,6 +441,9 @@ impl SpellTemplate {
 #[derive(Clone, Debug)]
 /// A spell struct.
 pub struct Spell {
+    /// The identifier of the spell, being its position in the spell pool starting at 1. 0 for unavailable spells.
+    id: u8,
+
     // -------- COSMETIC --------
     /// The name of the spell.
     name: String,
@@ -389,27 +462,56 @@ pub struct Spell {
 }
 
 impl Spell {
+    /// Returns the name of this spell.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Makes this spell block the passed factor as many spell slots as before.
+    /// As no caster can have more than [MAX_SPELL_SLOTS] slots, slots beyond that lengthen the blockage of the others instead.
+    pub fn scale_slot_cost(&mut self, factor: usize) {
+        let scaled: Vec<f32> = self
+            .spell_slots
+            .iter()
+            .flat_map(|&slot| std::iter::repeat_n(slot, factor))
+            .collect();
+        let count = scaled.len().min(MAX_SPELL_SLOTS);
+        let mut slots: TinyVec<[f32; MAX_SPELL_SLOTS]> = scaled[..count].iter().copied().collect();
+        for (index, &duration) in scaled[count..].iter().enumerate() {
+            slots[index % count] += duration;
+        }
+        self.spell_slots = slots;
+    }
+
+    /// Returns the amount of spell slots this spell blocks when cast.
+    pub fn get_slot_cost(&self) -> usize {
+        self.spell_slots.len()
+    }
+
     /// Constructs a new spell.
     fn new(
+        id: u8,
         name: &str,
         description: &str,
         icon: Sprite,
-        sound: impl Into<Option<&'static str>>,
+        sound: Option<String>,
         spell_: impl Into<ActionContainer>,
         spell_slots: TinyVec<[f32; MAX_SPELL_SLOTS]>,
     ) -> Self {
         Self {
+            id,
             name: name.to_owned(),
             description: description.to_owned(),
             icon,
             spell_: spell_.into(),
             spell_slots,
-            sound: sound.into().map(|s| s.to_owned()),
+            sound,
         }
     }
 
     fn not_available(sprite_pool: &mooeye::sprite::SpritePool, reason: &str) -> Self {
         Self {
+            id: 0,
             name: "Spell not available".to_owned(),
             description: reason.to_owned(),
             icon: sprite_pool
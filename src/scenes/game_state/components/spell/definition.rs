@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use mooeye::sprite::SpritePool;
+use serde::{Deserialize, Serialize};
+use tinyvec::TinyVec;
+
+use crate::scenes::game_state::achievements;
+
+use super::{ActionContainer, Spell, SpellTemplate, MAX_SPELL_SLOTS};
+
+/// The folder all spell definitions are loaded from, relative to the data folder.
+pub const SPELL_FOLDER: &str = "spells";
+
+/// A spell as defined in a data file, including its cost and how it is unlocked.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SpellDefinition {
+    /// The name of the spell.
+    pub name: String,
+    /// A short description of the spell to be displayed in the spell book.
+    pub description: String,
+    /// The path of the sprite representing the spell in the spell bar or spell book.
+    pub icon: String,
+    /// The variant of the icon sprite to display.
+    #[serde(default)]
+    pub icon_variant: u32,
+    /// The sound played on casting the spell.
+    #[serde(default)]
+    pub sound: Option<String>,
+    /// The durations of the spell slots this spell blocks when cast, in seconds.
+    pub slots: Vec<f32>,
+    /// The cost to unlock the spell.
+    pub cost: i32,
+    /// Wether the spell is purchased from the start.
+    #[serde(default)]
+    pub purchased: bool,
+    /// The required level of the mage's guild.
+    #[serde(default)]
+    pub guild_level: u8,
+    /// The id of the achievement that needs to be completed to unlock this spell.
+    #[serde(default)]
+    pub achievement: Option<String>,
+    /// The action that is added to the caster when casting the spell.
+    pub action: ActionContainer,
+}
+
+impl SpellDefinition {
+    /// Constructs the spell described by this definition, identified by the passed id.
+    pub fn to_spell(&self, sprite_pool: &SpritePool, id: u8) -> Spell {
+        let mut icon = sprite_pool
+            .init_sprite(&self.icon, Duration::ZERO)
+            .unwrap_or_default();
+        icon.set_variant(self.icon_variant);
+
+        let mut slots = TinyVec::new();
+        slots.extend(self.slots.iter().copied().take(MAX_SPELL_SLOTS));
+
+        Spell::new(
+            id,
+            &self.name,
+            &self.description,
+            icon,
+            self.sound.clone(),
+            self.action.clone(),
+            slots,
+        )
+    }
+
+    /// Constructs the spell template described by this definition, applying its unlock conditions.
+    pub fn to_template(
+        &self,
+        sprite_pool: &SpritePool,
+        achievements: &achievements::AchievementSet,
+        id: u8,
+    ) -> SpellTemplate {
+        let mut template = SpellTemplate::new(self.to_spell(sprite_pool, id), self.cost)
+            .guild_condition(self.guild_level);
+        if self.purchased {
+            template = template.purchased();
+        }
+        if let Some(id) = &self.achievement {
+            template = template.achievement_condition(achievements.get(id), sprite_pool);
+        }
+        template
+    }
+}
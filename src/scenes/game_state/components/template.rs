@@ -0,0 +1,289 @@
+use std::time::Duration;
+
+use ggez::glam::Vec2;
+use legion::{systems::CommandBuffer, Entity};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::super::GameRng;
+
+use super::{
+    actions::ActionEffect, duration::secs_option, Actions, Boss, BoundaryCollision, Collision,
+    Enemy, Graphics, Health, LifeDuration, Position, SpellOrigin, Velocity,
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+/// A description of an entity to be spawned into the world, consisting of the components it starts with.
+/// Templates replace spawning closures, so they can be contained in saved actions and data files.
+pub struct EntityTemplate {
+    /// The offset of the spawned entities to the spawning position.
+    #[serde(skip_serializing_if = "is_zero_vec")]
+    offset: Vec2,
+    /// The size of the area around the offset the spawned entities are randomly placed in.
+    #[serde(skip_serializing_if = "is_zero_vec")]
+    jitter: Vec2,
+    /// The amount of entities spawned.
+    #[serde(skip_serializing_if = "is_one")]
+    count: usize,
+
+    /// The velocity the spawned entities start with.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    velocity: Option<Velocity>,
+    /// The size of the range around the velocity the starting velocity is randomly chosen from.
+    #[serde(skip_serializing_if = "is_zero_vec")]
+    velocity_jitter: Vec2,
+    /// The time after which the spawned entities are removed.
+    #[serde(skip_serializing_if = "Option::is_none", with = "secs_option")]
+    life_duration: Option<Duration>,
+    /// The graphics of the spawned entities.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    graphics: Option<Graphics>,
+    /// The collision of the spawned entities.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    collision: Option<Collision>,
+    /// Wether the collision of the spawned entities ignores the entity that spawned them.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    source_immunity: bool,
+    /// Wether and how the spawned entities respect the boundaries of the game world.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    boundary_collision: Option<BoundaryCollision>,
+    /// The effects the spawned entities start with.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    effects: Vec<ActionEffect>,
+    /// The maximum health of the spawned entities.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    health: Option<i32>,
+    /// The enemy component of the spawned entities.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    enemy: Option<Enemy>,
+    /// The boss component of the spawned entities.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    boss: Option<Boss>,
+    /// The spell the spawned entities originate from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    origin: Option<u8>,
+}
+
+impl Default for EntityTemplate {
+    fn default() -> Self {
+        Self {
+            offset: Vec2::ZERO,
+            jitter: Vec2::ZERO,
+            count: 1,
+            velocity: None,
+            velocity_jitter: Vec2::ZERO,
+            life_duration: None,
+            graphics: None,
+            collision: None,
+            source_immunity: false,
+            boundary_collision: None,
+            effects: Vec::new(),
+            health: None,
+            enemy: None,
+            boss: None,
+            origin: None,
+        }
+    }
+}
+
+impl EntityTemplate {
+    /// Creates a new template spawning a single entity without any components but its position.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the offset of the spawned entities and returns the template builder-pattern style.
+    pub fn with_offset(mut self, x: f32, y: f32) -> Self {
+        self.offset = Vec2::new(x, y);
+        self
+    }
+
+    /// Sets the size of the area the spawned entities are randomly placed in and returns the template builder-pattern style.
+    pub fn with_jitter(mut self, x: f32, y: f32) -> Self {
+        self.jitter = Vec2::new(x, y);
+        self
+    }
+
+    /// Sets the amount of spawned entities and returns the template builder-pattern style.
+    pub fn with_count(mut self, count: usize) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Sets the velocity of the spawned entities and returns the template builder-pattern style.
+    pub fn with_velocity_vec(mut self, vel: Vec2) -> Self {
+        self.velocity = Some(Velocity::from(vel));
+        self
+    }
+
+    /// Sets the life duration of the spawned entities and returns the template builder-pattern style.
+    pub fn with_life_duration(mut self, duration: Duration) -> Self {
+        self.life_duration = Some(duration);
+        self
+    }
+
+    /// Sets the graphics of the spawned entities and returns the template builder-pattern style.
+    pub fn with_graphics(mut self, graphics: Graphics) -> Self {
+        self.graphics = Some(graphics);
+        self
+    }
+
+    /// Sets the collision of the spawned entities and returns the template builder-pattern style.
+    pub fn with_collision(mut self, collision: Collision) -> Self {
+        self.collision = Some(collision);
+        self
+    }
+
+    /// Sets the boundary collision of the spawned entities and returns the template builder-pattern style.
+    pub fn with_boundary_collision(mut self, boundary_collision: BoundaryCollision) -> Self {
+        self.boundary_collision = Some(boundary_collision);
+        self
+    }
+
+    /// Adds an effect the spawned entities start with and returns the template builder-pattern style.
+    pub fn with_effect(mut self, effect: ActionEffect) -> Self {
+        self.effects.push(effect);
+        self
+    }
+
+    /// Sets the maximum health of the spawned entities and returns the template builder-pattern style.
+    pub fn with_health(mut self, health: i32) -> Self {
+        self.health = Some(health);
+        self
+    }
+
+    /// Sets the enemy component of the spawned entities and returns the template builder-pattern style.
+    pub fn with_enemy(mut self, enemy: Enemy) -> Self {
+        self.enemy = Some(enemy);
+        self
+    }
+
+    /// Sets the boss component of the spawned entities and returns the template builder-pattern style.
+    pub fn with_boss(mut self, boss: Boss) -> Self {
+        self.boss = Some(boss);
+        self
+    }
+
+    /// Scales the maximum health of the spawned entities and returns the template builder-pattern style.
+    pub fn with_health_factor(mut self, factor: f32) -> Self {
+        self.health = self.health.map(|health| (health as f32 * factor) as i32);
+        self
+    }
+
+    /// Scales the bounty of the spawned enemies and returns the template builder-pattern style.
+    pub fn with_bounty_factor(mut self, factor: f32) -> Self {
+        self.enemy = self.enemy.map(|enemy| enemy.with_bounty_factor(factor));
+        self
+    }
+
+    /// Adds a particle to the graphics of the spawned entities and returns the template builder-pattern style.
+    pub fn with_particle(mut self, particle: super::graphics::Particle) -> Self {
+        self.graphics = self
+            .graphics
+            .map(|graphics| graphics.with_particle(particle));
+        self
+    }
+
+    /// Promotes the spawned enemies to elites, scaling their health, damage and bounty. Returns the template builder-pattern style.
+    pub fn with_elite(
+        mut self,
+        health_factor: f32,
+        damage_factor: i32,
+        bounty_factor: i32,
+    ) -> Self {
+        self.enemy = self
+            .enemy
+            .map(|enemy| enemy.with_elite(damage_factor, bounty_factor));
+        self.with_health_factor(health_factor)
+    }
+
+    /// Marks the spawned entities as originating from the passed spell, unless they already have an origin.
+    pub fn set_origin(&mut self, spell: u8) {
+        self.origin.get_or_insert(spell);
+    }
+
+    /// Spawns the entities described by this template relative to the passed position.
+    pub fn spawn(
+        &self,
+        source: Option<Entity>,
+        pos: Position,
+        cmd: &mut CommandBuffer,
+        rng: &mut GameRng,
+    ) {
+        for _ in 0..self.count {
+            let ent = cmd.push((
+                pos + self.offset + Self::roll_jitter(self.jitter, rng),
+                self.effects.iter().fold(Actions::new(), |actions, effect| {
+                    actions.with_effect(effect.clone())
+                }),
+            ));
+
+            if let Some(vel) = self.velocity {
+                cmd.add_component(
+                    ent,
+                    Velocity::from(Vec2::from(vel) + Self::roll_jitter(self.velocity_jitter, rng)),
+                );
+            }
+            if let Some(duration) = self.life_duration {
+                cmd.add_component(ent, LifeDuration::new(duration));
+            }
+            if let Some(graphics) = &self.graphics {
+                cmd.add_component(ent, graphics.clone());
+            }
+            if let Some(collision) = &self.collision {
+                cmd.add_component(
+                    ent,
+                    match source {
+                        Some(source) if self.source_immunity => {
+                            collision.clone().with_immunity(source)
+                        }
+                        _ => collision.clone(),
+                    },
+                );
+            }
+            if let Some(boundary_collision) = self.boundary_collision {
+                cmd.add_component(ent, boundary_collision);
+            }
+            if let Some(health) = self.health {
+                cmd.add_component(ent, Health::new(health));
+            }
+            if let Some(enemy) = self.enemy {
+                cmd.add_component(ent, enemy);
+            }
+            if let Some(boss) = &self.boss {
+                cmd.add_component(ent, boss.clone());
+            }
+            if let Some(origin) = self.origin {
+                cmd.add_component(ent, SpellOrigin(origin));
+            }
+        }
+    }
+
+    /// Returns a random vector within the rectangle of the passed size centered on zero.
+    /// Does not draw from the random number generator for axes of size zero.
+    fn roll_jitter(size: Vec2, rng: &mut GameRng) -> Vec2 {
+        Vec2::new(
+            if size.x != 0. {
+                (rng.gen::<f32>() - 0.5) * size.x
+            } else {
+                0.
+            },
+            if size.y != 0. {
+                (rng.gen::<f32>() - 0.5) * size.y
+            } else {
+                0.
+            },
+        )
+    }
+}
+
+/// Helper function to skip saving unused offsets and velocities.
+fn is_zero_vec(value: &Vec2) -> bool {
+    *value == Vec2::ZERO
+}
+
+/// Helper function to skip saving the default amount of spawned entities.
+fn is_one(value: &usize) -> bool {
+    *value == 1
+}
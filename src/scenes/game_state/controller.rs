@@ -1,8 +1,7 @@
 use ggez::{event::ScanCode, winit::event::VirtualKeyCode, Context};
-use std::{collections::HashMap, fs, path::Path, time::Duration};
+use std::{collections::HashMap, path::Path, time::Duration};
 
 use serde::{Deserialize, Serialize};
-use toml;
 
 #[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
 /// An enum containing all possible commands the user can give to the game.
@@ -34,6 +33,11 @@ impl Command {
             _ => Self::None,
         }
     }
+
+    /// Returns wether this command describes a continuous input (like movement) as opposed to a single event (like casting a spell).
+    pub fn is_continuous(&self) -> bool {
+        matches!(self, Self::MoveLeft | Self::MoveRight)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Eq, Hash, PartialEq)]
@@ -76,26 +80,14 @@ pub struct Controller {
     command_map: Vec<Mapping>,
 }
 
-impl Controller {
-    /// Loads a keymap from the given path and constructs a controller.
-    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
-        let string = fs::read_to_string(
-            path.as_ref()
-                .to_str()
-                .ok_or_else(|| ggez::GameError::CustomError("Could not read path.".to_owned()))?,
-        )?;
-        Ok(toml::from_str(&string)?)
-    }
+impl crate::storage::SaveFile for Controller {
+    const VERSION: u32 = 1;
+}
 
+impl Controller {
     /// Saves this controllers keymap to the given path.
     pub fn save_to_file(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn std::error::Error>> {
-        fs::write(
-            path.as_ref()
-                .to_str()
-                .ok_or_else(|| ggez::GameError::CustomError("Could not read path.".to_owned()))?,
-            toml::to_string(&self)?,
-        )?;
-        Ok(())
+        crate::storage::save(path, self)
     }
 
     /// Listens to all key presses in the context of the last frame and converts it to a list of commands given by the user as well as the time spent in the frame.
@@ -149,6 +141,7 @@ impl Default for Controller {
 }
 
 /// A struct that contains all (relevant interactions that happened in the last frame)
+#[derive(Clone, Debug)]
 pub struct Interactions {
     /// Keys pressed are directly mapped to the relevant commands given
     pub commands: HashMap<Command, bool>,
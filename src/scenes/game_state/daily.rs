@@ -0,0 +1,244 @@
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use super::{GameConfig, GameRng};
+
+/// The amount of modifiers active in every daily challenge.
+const MODIFIER_COUNT: usize = 2;
+/// The amount of spells the player starts a daily challenge with.
+const LOADOUT_SIZE: usize = 2;
+/// The length of a day in seconds.
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+/// The versions of the spells daily loadouts are drawn from, each with the first day it is used on and the spells by name.
+/// Names are resolved to spells when a challenge is built, so adding or reordering spell files doesn't change past challenges.
+/// A name listed several times stands for that many spells of the same name, in the order of their files.
+/// To offer new spells, add a version starting on a future day instead of changing an existing one.
+const SPELL_CANDIDATES: &[(u64, &[&str])] = &[(
+    0,
+    &[
+        "Fireball",
+        "Scorch",
+        "Fiery mortar",
+        "Flame Orb",
+        "Conflagrate",
+        "Summon Phoenix",
+        "Ice Bomb",
+        "Shard of Ice",
+        "Ice Lance",
+        "Lightning Ball",
+        "Overload",
+        "Lightning Ball",
+        "Gale Force",
+        "Airburst",
+        "Mind wipe",
+        "Blackhole",
+        "Arcane Blast",
+        "Arcane Missiles",
+    ],
+)];
+
+/// A daily challenge. Everyone playing on the same day plays with the same seed, spell loadout and modifiers.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DailyChallenge {
+    /// The date of the challenge, formatted as YYYY-MM-DD.
+    pub date: String,
+    /// The seed of the gameplay random number generator.
+    pub seed: u64,
+    /// The spells the player starts with, as indices into the spell pool.
+    pub spells: [usize; 4],
+    /// The modifiers active in this challenge.
+    pub modifiers: Vec<DailyModifier>,
+}
+
+impl DailyChallenge {
+    /// Returns the challenge of the current day (UTC).
+    pub fn today() -> Self {
+        Self::for_day(current_day())
+    }
+
+    /// Returns the challenge of the passed day, counted in days since the unix epoch.
+    pub fn for_day(day: u64) -> Self {
+        let mut rng = GameRng::seed_from_u64(day);
+
+        let modifiers = rand::seq::index::sample(
+            &mut rng,
+            DailyModifier::ALL.len(),
+            MODIFIER_COUNT.min(DailyModifier::ALL.len()),
+        )
+        .iter()
+        .map(|index| DailyModifier::ALL[index])
+        .collect();
+
+        // spell indices start at 1, 0 marks an empty slot
+        let mut spells = [0; 4];
+        let candidates = spell_candidates(day, &super::spell_names());
+        for (slot, index) in rand::seq::index::sample(
+            &mut rng,
+            candidates.len(),
+            LOADOUT_SIZE.min(candidates.len()),
+        )
+        .iter()
+        .enumerate()
+        {
+            spells[slot] = candidates[index];
+        }
+
+        Self {
+            date: format_date(day),
+            // keep the seed short, as described in GameConfig::random_seed
+            seed: rng.gen::<u32>() as u64,
+            spells,
+            modifiers,
+        }
+    }
+
+    /// Constructs the config of a game playing this challenge.
+    pub fn to_config(&self) -> GameConfig {
+        let mut config = GameConfig {
+            base_spells: self.spells,
+            seed: Some(self.seed),
+            daily: Some(self.clone()),
+            ..Default::default()
+        };
+        for modifier in &self.modifiers {
+            modifier.apply(&mut config);
+        }
+        config
+    }
+}
+
+/// A modifier changing the rules of a daily challenge.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DailyModifier {
+    /// The player starts with additional gold.
+    Wealthy,
+    /// The city starts with half its health.
+    Fragile,
+    /// The director earns more credits from the start.
+    Horde,
+    /// The credits the director earns grow faster with every wave.
+    Escalation,
+    /// The player starts with an additional spell slot.
+    Attuned,
+    /// The game starts at a later wave.
+    Headstart,
+    /// The player moves faster.
+    Swift,
+    /// The first wave consists of faster enemies.
+    Vanguard,
+}
+
+impl DailyModifier {
+    /// All modifiers a daily challenge can be created with.
+    pub const ALL: [Self; 8] = [
+        Self::Wealthy,
+        Self::Fragile,
+        Self::Horde,
+        Self::Escalation,
+        Self::Attuned,
+        Self::Headstart,
+        Self::Swift,
+        Self::Vanguard,
+    ];
+
+    /// Returns the name of this modifier.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Wealthy => "Wealthy",
+            Self::Fragile => "Fragile",
+            Self::Horde => "Horde",
+            Self::Escalation => "Escalation",
+            Self::Attuned => "Attuned",
+            Self::Headstart => "Headstart",
+            Self::Swift => "Swift",
+            Self::Vanguard => "Vanguard",
+        }
+    }
+
+    /// Returns a short description of this modifier.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Wealthy => "Start with 300 gold.",
+            Self::Fragile => "The city starts with half its health.",
+            Self::Horde => "Enemies arrive in greater numbers.",
+            Self::Escalation => "Waves grow stronger more quickly.",
+            Self::Attuned => "Start with an additional spell slot.",
+            Self::Headstart => "Start at wave 5.",
+            Self::Swift => "Move faster.",
+            Self::Vanguard => "The first wave brings faster enemies.",
+        }
+    }
+
+    /// Applies this modifier to the passed game config.
+    pub fn apply(&self, config: &mut GameConfig) {
+        match self {
+            Self::Wealthy => config.starting_gold += 300,
+            Self::Fragile => config.starting_city_health = (config.starting_city_health / 2).max(1),
+            Self::Horde => config.base_credits *= 1.5,
+            Self::Escalation => config.wave_credits *= 1.5,
+            Self::Attuned => config.base_slots += 1,
+            Self::Headstart => config.starting_wave = config.starting_wave.max(5),
+            Self::Swift => config.base_speed *= 1.25,
+            Self::Vanguard => config.wave_enemies = [1, 2, 3, 3],
+        }
+    }
+}
+
+/// Returns the spells the loadout of the passed day is drawn from, as indices into the spell pool.
+/// The passed spell names are ordered by their index minus one. Candidates without a matching spell are reported and skipped.
+fn spell_candidates(day: u64, spell_names: &[String]) -> Vec<usize> {
+    let names = SPELL_CANDIDATES
+        .iter()
+        .rev()
+        .find(|(first_day, _)| *first_day <= day)
+        .map(|(_, candidates)| *candidates)
+        .unwrap_or_default();
+
+    let mut candidates: Vec<usize> = Vec::new();
+    for name in names {
+        // spells sharing a name are resolved in order, skipping those already taken
+        match (1..=spell_names.len())
+            .find(|&index| spell_names[index - 1] == *name && !candidates.contains(&index))
+        {
+            Some(index) => candidates.push(index),
+            None => println!(
+                "[ERROR/Radish] Daily challenge spell {} is not defined.",
+                name
+            ),
+        }
+    }
+    candidates
+}
+
+/// Returns the current day (UTC), counted in days since the unix epoch.
+fn current_day() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|time| time.as_secs() / SECONDS_PER_DAY)
+        .unwrap_or_default()
+}
+
+/// Returns the current date (UTC), formatted as YYYY-MM-DD.
+pub fn current_date() -> String {
+    format_date(current_day())
+}
+
+/// Formats the passed day, counted in days since the unix epoch, as YYYY-MM-DD.
+fn format_date(day: u64) -> String {
+    // convert to the proleptic gregorian calendar, counting eras of 400 years from the year 0
+    let days = day as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day_of_month = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day_of_month)
+}
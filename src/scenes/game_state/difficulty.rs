@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+
+use super::{components::EntityTemplate, GameConfig};
+
+/// A difficulty level, scaling the director, the city and the enemies of a run.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Difficulty {
+    /// Fewer and weaker enemies, a sturdier city and more gold.
+    Easy,
+    /// The game as it was designed.
+    #[default]
+    Normal,
+    /// More and tougher enemies, a weaker city and less gold.
+    Hard,
+    /// Even more and even tougher enemies, for players that have mastered hard.
+    Nightmare,
+}
+
+impl Difficulty {
+    /// Returns the name of this difficulty.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Easy => "Easy",
+            Self::Normal => "Normal",
+            Self::Hard => "Hard",
+            Self::Nightmare => "Nightmare",
+        }
+    }
+
+    /// Returns the next harder difficulty, wrapping around to the easiest one.
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Easy => Self::Normal,
+            Self::Normal => Self::Hard,
+            Self::Hard => Self::Nightmare,
+            Self::Nightmare => Self::Easy,
+        }
+    }
+
+    /// Returns the factor the credits the director earns are multiplied with.
+    pub fn credit_factor(&self) -> f32 {
+        match self {
+            Self::Easy => 0.8,
+            Self::Normal => 1.,
+            Self::Hard => 1.25,
+            Self::Nightmare => 1.5,
+        }
+    }
+
+    /// Returns the factor the starting health of the city is multiplied with.
+    pub fn city_health_factor(&self) -> f32 {
+        match self {
+            Self::Easy => 1.5,
+            Self::Normal => 1.,
+            Self::Hard => 0.7,
+            Self::Nightmare => 0.5,
+        }
+    }
+
+    /// Returns the amount of gold the player starts with in addition to the configured starting gold.
+    pub fn starting_gold(&self) -> i32 {
+        match self {
+            Self::Easy => 100,
+            _ => 0,
+        }
+    }
+
+    /// Returns the factor the maximum health of all enemies is multiplied with.
+    pub fn enemy_health_factor(&self) -> f32 {
+        match self {
+            Self::Easy => 0.75,
+            Self::Normal => 1.,
+            Self::Hard => 1.3,
+            Self::Nightmare => 1.7,
+        }
+    }
+
+    /// Returns the factor the bounty of all enemies is multiplied with.
+    pub fn bounty_factor(&self) -> f32 {
+        match self {
+            Self::Easy => 1.25,
+            Self::Normal => 1.,
+            Self::Hard => 0.9,
+            Self::Nightmare => 0.8,
+        }
+    }
+
+    /// Scales the director credits, city health and starting gold of the passed config.
+    pub fn apply(&self, config: &mut GameConfig) {
+        config.base_credits *= self.credit_factor();
+        config.wave_credits *= self.credit_factor();
+        config.starting_city_health =
+            ((config.starting_city_health as f32 * self.city_health_factor()).round() as i32)
+                .max(1);
+        config.starting_gold += self.starting_gold();
+    }
+
+    /// Scales the health and bounty of the enemies spawned from the passed template.
+    pub fn scale_template(&self, template: EntityTemplate) -> EntityTemplate {
+        if *self == Self::Normal {
+            return template;
+        }
+        template
+            .with_health_factor(self.enemy_health_factor())
+            .with_bounty_factor(self.bounty_factor())
+    }
+}
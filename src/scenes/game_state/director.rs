@@ -1,19 +1,32 @@
-use std::{fmt::Debug, time::Duration};
+use std::time::Duration;
 
+use super::difficulty::Difficulty;
+use super::mutators::Mutator;
 use super::*;
 use legion::{system, systems::CommandBuffer};
 use mooeye::sprite;
-use rand::random;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 
 /// The maximum amount of different enemy templates per wave
 pub(super) const WAVE_SIZE: usize = 4;
+/// Every this many waves, a boss is spawned at the start of the wave.
+pub(super) const BOSS_WAVE_INTERVAL: u32 = 5;
 
-mod descriptor;
-mod spawners;
+/// Module containing the data file definitions of bosses.
+mod boss;
+/// Module containing the authored waves of campaigns.
+mod campaign;
+/// Module containing the data file definitions of enemies.
+mod definition;
+/// Module containing the promotion of spawns to elites and their affixes.
+mod elite;
+pub use boss::BossDescriptor;
+pub use campaign::{Campaign, CAMPAIGN_PATH};
 /// The director struct is responsible for spawning waves of enemies.
 /// A director regularly earns credit points and spends them on units from a customizable enemy set until a wave threshhold is reached.
 /// Then, the director rerolls the enemy pool and starts a new wave.
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Director {
     // --- ONGOING VALUES ---
     /// The current wave number.
@@ -28,10 +41,29 @@ pub struct Director {
 
     /// The enemies
     wave_enemies: [usize; WAVE_SIZE],
-    /// The enemy posse the director can select spawns from, containing their costs and a spawning function pointer.
+    /// The enemy posse the director can select spawns from, containing their costs and spawning templates.
+    /// Not saved, as it is restored from the sprite pool via [Director::with_enemies].
+    #[serde(skip)]
     enemies: Vec<EnemyDescriptor>,
     /// The cost to reroll the current enemy selection
     reroll_cost: i32,
+    /// The bosses the director spawns in boss waves, in order.
+    /// Not saved, as they are restored from the sprite pool via [Director::with_enemies].
+    #[serde(skip)]
+    bosses: Vec<BossDescriptor>,
+    /// The affixes elite enemies can be spawned with.
+    /// Not saved, as they are restored via [Director::with_enemies].
+    #[serde(skip)]
+    affixes: Vec<elite::EliteAffix>,
+    /// The campaign whose waves the director spawns. If none is set, the director spawns endless random waves.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    campaign: Option<Campaign>,
+    /// Wether every spawned enemy is promoted to an elite.
+    #[serde(default)]
+    all_elite: bool,
+    /// The difficulty scaling the health and bounty of all enemies.
+    #[serde(default)]
+    difficulty: Difficulty,
 
     // --- CONFIGURATION ---
     /// The base amounts of credits per second
@@ -43,52 +75,187 @@ pub struct Director {
 impl Director {
     /// Spawns a new director with default parameters.
     pub fn new(sprite_pool: &sprite::SpritePool, config: &super::GameConfig) -> Self {
-        Self {
+        let campaign = config.campaign.as_ref().and_then(|path| {
+            crate::data::from_path::<Campaign>(crate::paths::data_path(path))
+                .map_err(|e| println!("[ERROR/Radish] Could not load campaign {}: {}", path, e))
+                .ok()
+        });
+
+        let mut director = Self {
             wave: (config.starting_wave - 1).max(1),
-            state: if config.starting_wave <= 1 {
-                DirectorState::Spawning(450)
-            } else {
-                DirectorState::WaitingForDead
-            },
+            state: DirectorState::WaitingForDead,
 
             intervall: Duration::ZERO,
             credits: 0,
 
             wave_enemies: config.wave_enemies,
-            enemies: descriptor::generate_descriptors(sprite_pool).unwrap_or_default(),
+            enemies: definition::load_descriptors(sprite_pool),
             reroll_cost: 30,
+            bosses: boss::load_bosses(sprite_pool),
+            affixes: elite::load_affixes(),
+            campaign,
+            all_elite: config.mutators.contains(&Mutator::EliteHorde),
+            difficulty: config.difficulty,
 
             base_credits: config.base_credits,
             wave_credits: config.wave_credits,
+        };
+
+        if config.starting_wave <= 1 {
+            director.state = if director.campaign.is_some() {
+                director.wave_start_state(0)
+            } else {
+                DirectorState::Spawning(450)
+            };
+        }
+
+        director.check_wave_enemies();
+        director.scale_enemies();
+        director
+    }
+
+    /// Restores the enemy posse and elite affixes of a director loaded from a save game and returns it builder-pattern style.
+    pub fn with_enemies(mut self, sprite_pool: &sprite::SpritePool) -> Self {
+        self.enemies = definition::load_descriptors(sprite_pool);
+        self.bosses = boss::load_bosses(sprite_pool);
+        self.affixes = elite::load_affixes();
+        self.check_wave_enemies();
+        self.scale_enemies();
+        self
+    }
+
+    /// Reports an error if the enemy posse is empty or the enemies of the current wave refer to enemies that are not defined.
+    /// Such enemies are never spawned.
+    fn check_wave_enemies(&self) {
+        if self.enemies.is_empty() {
+            println!("[ERROR/Radish] No enemies are defined, so no enemies will be spawned.");
+        }
+        for &index in &self.wave_enemies {
+            if index >= self.enemies.len() {
+                println!(
+                    "[ERROR/Radish] Wave enemy {} is not defined, as only {} enemies are.",
+                    index,
+                    self.enemies.len()
+                );
+            }
         }
     }
 
+    /// Scales the health and bounty of all enemies and bosses according to the difficulty of this director.
+    fn scale_enemies(&mut self) {
+        let difficulty = self.difficulty;
+        for enemy in &mut self.enemies {
+            enemy.template = difficulty.scale_template(enemy.template.clone());
+        }
+        for boss in &mut self.bosses {
+            boss.template = difficulty.scale_template(boss.template.clone());
+        }
+    }
+
+    /// Returns the difficulty of the run this director spawns enemies for.
+    pub fn get_difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
     /// Returns the current wave.
     pub fn get_wave(&self) -> u32 {
         self.wave
     }
 
-    /// Returns a reference with the current wave's enemies
-    pub fn get_enemies(&self) -> [&EnemyDescriptor; WAVE_SIZE] {
-        self.wave_enemies.map(|i| &self.enemies[i])
+    /// Returns references to the enemies of the next wave.
+    /// In a campaign, these are the enemies authored for that wave, otherwise the current random selection.
+    pub fn get_enemies(&self) -> Vec<&EnemyDescriptor> {
+        match &self.campaign {
+            Some(campaign) => campaign
+                .get_wave(self.wave + 1)
+                .map(|wave| {
+                    wave.enemy_names()
+                        .into_iter()
+                        .filter_map(|name| self.get_enemy_by_name(name))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            None => self
+                .wave_enemies
+                .iter()
+                .filter_map(|&i| self.enemies.get(i))
+                .collect(),
+        }
+    }
+
+    /// Returns the enemy with the passed name.
+    fn get_enemy_by_name(&self, name: &str) -> Option<&EnemyDescriptor> {
+        self.enemies.iter().find(|enemy| enemy.name == name)
+    }
+
+    /// Returns the campaign this director follows, if any.
+    pub fn get_campaign(&self) -> Option<&Campaign> {
+        self.campaign.as_ref()
+    }
+
+    /// Returns the id of the boss spawned in the passed wave, if it is a boss wave.
+    /// In a campaign, bosses are spawned as authored. Otherwise, they are spawned every few waves
+    /// in the order they are defined in, starting over once all have been spawned.
+    fn get_boss_id(&self, wave: u32) -> Option<usize> {
+        if let Some(campaign) = &self.campaign {
+            let name = campaign.get_wave(wave)?.boss.as_ref()?;
+            return self.bosses.iter().position(|boss| &boss.name == name);
+        }
+        if wave == 0 || !wave.is_multiple_of(BOSS_WAVE_INTERVAL) || self.bosses.is_empty() {
+            None
+        } else {
+            Some((wave / BOSS_WAVE_INTERVAL - 1) as usize % self.bosses.len())
+        }
+    }
+
+    /// Returns the boss spawned in the passed wave, if it is a boss wave.
+    pub fn get_boss(&self, wave: u32) -> Option<&BossDescriptor> {
+        self.get_boss_id(wave).map(|id| &self.bosses[id])
+    }
+
+    /// Returns the boss with the passed id.
+    pub fn get_boss_by_id(&self, id: u8) -> Option<&BossDescriptor> {
+        self.bosses.get(id as usize)
     }
 
     /// If currently in the last [DirectorState] of a wave cycle, reset to the first one, increase the wave number
-    /// and grant a wave_pool for that next wave.
+    /// and grant a wave_pool for that next wave. Boss waves spawn only half the usual pool besides their boss.
     pub fn next_wave(&mut self) {
         if self.state == DirectorState::WaitingForMenu {
             self.wave += 1;
-            self.state = DirectorState::Spawning(
-                30 * (self.base_credits + 2. * self.wave_credits * self.wave as f32) as u32,
-            );
+            let wave_pool =
+                30 * (self.base_credits + 2. * self.wave_credits * self.wave as f32) as u32;
+            self.state = self.wave_start_state(wave_pool);
+        }
+    }
+
+    /// Returns the state the current wave starts in, given the wave_pool of a random wave.
+    fn wave_start_state(&self, wave_pool: u32) -> DirectorState {
+        if self.get_boss(self.wave).is_some() {
+            DirectorState::SpawningBoss(wave_pool / 2)
+        } else {
+            self.spawning_state(wave_pool)
+        }
+    }
+
+    /// Returns the state spawning the regular enemies of the current wave, given the wave_pool of a random wave.
+    fn spawning_state(&self, wave_pool: u32) -> DirectorState {
+        if self.campaign.is_some() {
+            DirectorState::Scripted(Duration::ZERO)
+        } else {
+            DirectorState::Spawning(wave_pool)
         }
     }
 
     /// Rerolls the currently selected enemies (and increases reroll cost).
-    pub fn reroll_wave_enemies(&mut self) {
+    /// Does nothing if there are no enemies to select from.
+    pub fn reroll_wave_enemies(&mut self, rng: &mut GameRng) {
+        if self.enemies.is_empty() {
+            return;
+        }
         // get 4 random indices of enemies
         for i in 0..WAVE_SIZE {
-            self.wave_enemies[i] = rand::random::<usize>() % self.enemies.len();
+            self.wave_enemies[i] = rng.gen::<usize>() % self.enemies.len();
         }
         self.reroll_cost += 20;
         // sort the wave_enemies array
@@ -104,10 +271,16 @@ impl Director {
     pub fn is_between_waves(&self) -> bool {
         self.state == DirectorState::WaitingForMenu
     }
+
+    /// Returns wether the last wave of the campaign has been beaten.
+    pub fn is_victorious(&self) -> bool {
+        self.state == DirectorState::Victorious
+    }
 }
 
 /// A system that handles the directors interaction with the game world.
 /// This increases the director credits and spends them, handles unit spawning and sends messages to initialize new waves.
+#[allow(clippy::too_many_arguments)]
 #[system]
 pub fn direct(
     subworld: &mut legion::world::SubWorld,
@@ -115,14 +288,62 @@ pub fn direct(
     cmd: &mut CommandBuffer,
     #[resource] boundaries: &graphics::Rect,
     #[resource] director: &mut Director,
+    #[resource] rng: &mut GameRng,
     #[resource] ix: &controller::Interactions,
-    #[resource] messages: &mut MessageSet,
+    #[resource] messages: &mut MessageQueue,
 ) {
     // add time since last frame to counters
 
     director.intervall += ix.delta;
 
     match director.state {
+        DirectorState::SpawningBoss(wave_pool) => {
+            if let Some(id) = director.get_boss_id(director.wave) {
+                let pos = ggez::glam::Vec2::new(boundaries.w / 2., -20.);
+                director.bosses[id].template.spawn(None, pos, cmd, rng);
+                messages.push(mooeye::ui::UiMessage::Extern(GameMessage::BossSpawned {
+                    boss: id as u8,
+                }));
+            }
+            director.state = director.spawning_state(wave_pool);
+        }
+        DirectorState::Scripted(elapsed) => {
+            let now = elapsed + ix.delta;
+            if let Some(wave) = director
+                .campaign
+                .as_ref()
+                .and_then(|campaign| campaign.get_wave(director.wave))
+            {
+                for spawn in &wave.spawn {
+                    let amount = spawn.spawns_between(elapsed, now);
+                    if amount == 0 {
+                        continue;
+                    }
+                    match director.get_enemy_by_name(&spawn.enemy) {
+                        Some(enemy_descriptor) => {
+                            let pos = ggez::glam::Vec2::new(spawn.x * boundaries.w, -20.);
+                            for _ in 0..amount {
+                                enemy_descriptor.template.spawn(None, pos, cmd, rng);
+                            }
+                        }
+                        None => println!(
+                            "[ERROR/Radish] Campaign references unknown enemy {}.",
+                            spawn.enemy
+                        ),
+                    }
+                }
+            }
+            director.state = if director
+                .campaign
+                .as_ref()
+                .and_then(|campaign| campaign.get_wave(director.wave))
+                .is_some_and(|wave| now <= wave.duration())
+            {
+                DirectorState::Scripted(now)
+            } else {
+                DirectorState::WaitingForDead
+            };
+        }
         DirectorState::Spawning(wave_pool) => {
             // only spawn in 1-second intervalls
             if director.intervall >= Duration::from_secs(1) {
@@ -133,12 +354,12 @@ pub fn direct(
                 director.intervall = Duration::ZERO;
 
                 // randomly select an amount of available credits to spend
-                let mut to_spend = (random::<f32>().powi(2) * director.credits as f32) as u32;
+                let mut to_spend = (rng.gen::<f32>().powi(2) * director.credits as f32) as u32;
 
                 // while credits left to spend
                 'outer: loop {
                     // select a random enemy type
-                    let mut enemy_ind = random::<usize>() % director.wave_enemies.len();
+                    let mut enemy_ind = rng.gen::<usize>() % director.wave_enemies.len();
                     let mut enemy = director.enemies.get(director.wave_enemies[enemy_ind]);
 
                     // downgrade spawn until affordable
@@ -158,10 +379,21 @@ pub fn direct(
                     // unpack enemy
                     if let Some(enemy_descriptor) = enemy {
                         // spawn
-                        (enemy_descriptor.spawner._spawner)(
-                            cmd,
-                            ggez::glam::Vec2::new(rand::random::<f32>() * boundaries.w, -20.),
-                        );
+                        let pos = ggez::glam::Vec2::new(rng.gen::<f32>() * boundaries.w, -20.);
+                        // possibly promote the spawn to an elite
+                        if !director.affixes.is_empty()
+                            && (director.all_elite || elite::roll_elite(director.wave, rng))
+                        {
+                            elite::promote(
+                                &enemy_descriptor.template,
+                                &director.affixes,
+                                director.wave,
+                                rng,
+                            )
+                            .spawn(None, pos, cmd, rng);
+                        } else {
+                            enemy_descriptor.template.spawn(None, pos, cmd, rng);
+                        }
 
                         // reduce available credits
                         to_spend -= enemy_descriptor.cost;
@@ -180,31 +412,76 @@ pub fn direct(
         }
         DirectorState::WaitingForDead => {
             if enemy_query.iter(subworld).count() == 0 {
-                messages.insert(mooeye::ui::UiMessage::Extern(GameMessage::NextWave(
-                    director.wave as i32 + 1,
-                )));
-                director.reroll_wave_enemies();
-                director.reroll_cost = 30;
-                director.state = DirectorState::WaitingForMenu
+                if director
+                    .campaign
+                    .as_ref()
+                    .is_some_and(|campaign| campaign.is_last_wave(director.wave))
+                {
+                    // the campaign is won once its last wave has been cleared
+                    messages.push(mooeye::ui::UiMessage::Extern(GameMessage::CampaignWon));
+                    director.state = DirectorState::Victorious;
+                } else {
+                    messages.push(mooeye::ui::UiMessage::Extern(GameMessage::NextWave(
+                        director.wave as i32 + 1,
+                    )));
+                    director.reroll_wave_enemies(rng);
+                    director.reroll_cost = 30;
+                    director.state = DirectorState::WaitingForMenu
+                }
             }
         }
-        DirectorState::WaitingForMenu => {}
+        DirectorState::WaitingForMenu | DirectorState::Victorious => {}
     }
 }
 
 /// The state of a [Director].
 /// States should be used only in sequence.
 /// One rotation = one wave.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 enum DirectorState {
+    /// The director is about to spawn the boss of this wave.
+    /// The payload is the wave_pool to spawn after the boss.
+    SpawningBoss(u32),
     /// The director is currently spawning enemies.
     /// The payload is the wave_pool left to spawn until this wave ends.
     Spawning(u32),
+    /// The director is spawning the authored enemies of a campaign wave.
+    /// The payload is the time passed since the wave started.
+    Scripted(#[serde(with = "components::duration::secs")] Duration),
     /// The director has emptied its wave pool and is waiting for all spawned enemies to be removed.
     WaitingForDead,
     /// All enemies have despawned and the director has notified the player of the end of the wave.
     /// The director is waiting for the player to init the next wave.
     WaitingForMenu,
+    /// The last wave of the campaign has been beaten. No more waves follow.
+    Victorious,
+}
+
+/// Returns the names of all enemies and bosses defined in the data folder by their identifier.
+/// Reports an error if several enemies share an identifier, as their kills could not be told apart.
+pub fn enemy_names() -> std::collections::HashMap<u8, String> {
+    let mut names = std::collections::HashMap::new();
+    for enemy in crate::data::load_folder::<definition::EnemyDefinition>(
+        crate::paths::data_path(definition::ENEMY_FOLDER),
+        "enemy",
+    )
+    .into_iter()
+    .chain(
+        crate::data::load_folder::<boss::BossDefinition>(
+            crate::paths::data_path(boss::BOSS_FOLDER),
+            "boss",
+        )
+        .into_iter()
+        .map(|boss| boss.enemy),
+    ) {
+        if let Some(other) = names.insert(enemy.id, enemy.name.clone()) {
+            println!(
+                "[ERROR/Radish] {} and {} share the id {}.",
+                other, enemy.name, enemy.id
+            );
+        }
+    }
+    names
 }
 
 #[derive(Debug, Clone)]
@@ -218,8 +495,8 @@ pub struct EnemyDescriptor {
     pub description: String,
     /// The spawning cost of the enemy, determining its frequency.
     cost: u32,
-    /// A wrapped function pointer to spawn the enemy.
-    spawner: EnemySpawner,
+    /// The template the enemy is spawned from.
+    template: components::EntityTemplate,
 }
 
 impl EnemyDescriptor {
@@ -229,28 +506,14 @@ impl EnemyDescriptor {
         name: &str,
         description: &str,
         cost: u32,
-        spawner: EnemySpawnFunction,
+        template: components::EntityTemplate,
     ) -> Self {
         Self {
             icon,
             name: name.to_owned(),
             description: description.to_owned(),
             cost,
-            spawner: EnemySpawner { _spawner: spawner },
+            template,
         }
     }
 }
-
-/// Contains a function pointer to spawn an enemy
-#[derive(Clone)]
-struct EnemySpawner {
-    _spawner: EnemySpawnFunction,
-}
-
-impl Debug for EnemySpawner {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("EnemySpawner").finish()
-    }
-}
-
-type EnemySpawnFunction = fn(&mut CommandBuffer, components::Position) -> ();
@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use mooeye::sprite::{self, SpritePool};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    components::{
+        self,
+        actions::{ActionContainer, ActionEffect, ActionEffectTarget, RemoveSource},
+        boss::BossPhase,
+    },
+    definition::EnemyDefinition,
+};
+
+/// The folder all boss definitions are loaded from, relative to the data folder.
+pub const BOSS_FOLDER: &str = "bosses";
+
+/// Loads the descriptors of all bosses defined in the boss folder.
+/// The position of a boss in the returned list is its id.
+pub(super) fn load_bosses(sprite_pool: &SpritePool) -> Vec<BossDescriptor> {
+    crate::data::load_folder::<BossDefinition>(crate::paths::data_path(BOSS_FOLDER), "boss")
+        .iter()
+        .enumerate()
+        .map(|(id, definition)| definition.to_descriptor(sprite_pool, id as u8))
+        .collect()
+}
+
+/// A boss as defined in a data file: An enemy with attack phases and a reward on kill.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BossDefinition {
+    /// The enemy the boss is based on.
+    #[serde(flatten)]
+    pub enemy: EnemyDefinition,
+    /// The phases the boss enters once its health drops below their thresholds.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub phases: Vec<BossPhase>,
+    /// The actions applied to the boss when it is killed, in addition to its bounty.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reward: Option<ActionContainer>,
+}
+
+impl BossDefinition {
+    /// Constructs the descriptor of this boss, identified by the passed id.
+    pub fn to_descriptor(&self, sprite_pool: &SpritePool, id: u8) -> BossDescriptor {
+        let mut template = self
+            .enemy
+            .to_template()
+            .with_boss(components::Boss::new(id, self.phases.clone()));
+        if let Some(reward) = &self.reward {
+            template = template.with_effect(ActionEffect::on_death(
+                ActionEffectTarget::new_only_self(),
+                RemoveSource::HealthLoss,
+                reward.clone(),
+            ));
+        }
+
+        BossDescriptor {
+            icon: sprite_pool
+                .init_sprite(&self.enemy.sprite, Duration::ZERO)
+                .unwrap_or_default(),
+            name: self.enemy.name.clone(),
+            description: self.enemy.description.clone(),
+            template,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// A descriptor describing a boss and how to spawn it.
+pub struct BossDescriptor {
+    /// The icon of this boss, to be displayed in the wave menu.
+    pub icon: sprite::Sprite,
+    /// The name of the boss, displayed above its health bar.
+    pub name: String,
+    /// A short description of the bosses abilities.
+    pub description: String,
+    /// The template the boss is spawned from.
+    pub(super) template: components::EntityTemplate,
+}
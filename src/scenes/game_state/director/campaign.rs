@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::components::duration::secs;
+
+/// The path of the campaign started from the main menu, relative to the data folder.
+pub const CAMPAIGN_PATH: &str = "campaign.toml";
+
+/// A campaign: A fixed list of authored waves, won by surviving the last one.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Campaign {
+    /// The name of the campaign, also used to record its completion.
+    pub name: String,
+    /// A short description of the campaign.
+    pub description: String,
+    /// The waves of the campaign, in order.
+    pub wave: Vec<CampaignWave>,
+}
+
+impl Campaign {
+    /// Returns the passed wave of this campaign, counting from 1.
+    pub fn get_wave(&self, wave: u32) -> Option<&CampaignWave> {
+        self.wave.get((wave as usize).checked_sub(1)?)
+    }
+
+    /// Returns wether the passed wave is the last one of this campaign.
+    pub fn is_last_wave(&self, wave: u32) -> bool {
+        wave as usize >= self.wave.len()
+    }
+}
+
+/// A single authored wave of a [Campaign].
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CampaignWave {
+    /// The name of the boss spawned at the start of this wave, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub boss: Option<String>,
+    /// The groups of enemies spawned during this wave.
+    #[serde(default)]
+    pub spawn: Vec<CampaignSpawn>,
+}
+
+impl CampaignWave {
+    /// Returns the time after which all enemies of this wave have been spawned.
+    pub fn duration(&self) -> Duration {
+        self.spawn
+            .iter()
+            .map(|spawn| spawn.time + spawn.interval * spawn.count.saturating_sub(1) as u32)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Returns the names of all enemies spawned during this wave, without duplicates.
+    pub fn enemy_names(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        for spawn in &self.spawn {
+            if !names.contains(&spawn.enemy.as_str()) {
+                names.push(spawn.enemy.as_str());
+            }
+        }
+        names
+    }
+}
+
+/// A group of identical enemies spawned at a fixed time and position during a [CampaignWave].
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CampaignSpawn {
+    /// The name of the enemy spawned.
+    pub enemy: String,
+    /// The amount of enemies spawned.
+    #[serde(default = "default_count")]
+    pub count: usize,
+    /// The time after the start of the wave the first enemy is spawned at, in seconds.
+    #[serde(with = "secs")]
+    pub time: Duration,
+    /// The time between two consecutive enemies of this group, in seconds.
+    #[serde(default, with = "secs")]
+    pub interval: Duration,
+    /// The horizontal spawning position, as a fraction of the width of the street.
+    pub x: f32,
+}
+
+impl CampaignSpawn {
+    /// Returns the amount of enemies of this group spawned in the time span from `from` (inclusive) to `to` (exclusive).
+    pub fn spawns_between(&self, from: Duration, to: Duration) -> usize {
+        (0..self.count)
+            .map(|i| self.time + self.interval * i as u32)
+            .filter(|time| *time >= from && *time < to)
+            .count()
+    }
+}
+
+/// Helper function for groups that don't specify their amount of enemies.
+fn default_count() -> usize {
+    1
+}
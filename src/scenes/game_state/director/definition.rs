@@ -0,0 +1,106 @@
+use std::time::Duration;
+
+use ggez::glam::Vec2;
+use mooeye::sprite::SpritePool;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    components::{self, actions::ActionEffect, duration::secs_option, EntityTemplate},
+    EnemyDescriptor,
+};
+
+/// The folder all enemy definitions are loaded from, relative to the data folder.
+pub const ENEMY_FOLDER: &str = "enemies";
+
+/// Loads the descriptors of all enemies defined in the enemy folder.
+pub(super) fn load_descriptors(sprite_pool: &SpritePool) -> Vec<EnemyDescriptor> {
+    crate::data::load_folder::<EnemyDefinition>(crate::paths::data_path(ENEMY_FOLDER), "enemy")
+        .iter()
+        .map(|definition| definition.to_descriptor(sprite_pool))
+        .collect()
+}
+
+/// An enemy as defined in a data file, including its spawning cost and wave menu description.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EnemyDefinition {
+    /// The name of the enemy.
+    pub name: String,
+    /// A short description of the enemies abilities.
+    pub description: String,
+    /// The path of the sprite of the enemy, used both ingame and as icon in the wave menu.
+    pub sprite: String,
+    /// The time each frame of the sprite is displayed, in seconds.
+    pub frame_time: f32,
+    /// The spawning cost of the enemy, determining its frequency. Not needed for bosses.
+    #[serde(default)]
+    pub cost: u32,
+
+    /// The maximum health of the enemy.
+    pub health: i32,
+    /// The damage the enemy deals to the city if it reaches the finish line.
+    pub damage: i32,
+    /// The bounty granted on killing this enemy.
+    pub bounty: i32,
+    /// An identifier to check for certain enemy kills.
+    pub id: u8,
+    /// The velocity of the enemy in pixels per second.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub velocity: Option<Vec2>,
+    /// The width and height of the collision box of the enemy.
+    pub size: (f32, f32),
+    /// Wether and how the enemy respects the boundaries of the game world.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub boundary_collision: Option<components::BoundaryCollision>,
+    /// The offset of the enemies spawning position to the top of the screen.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<Vec2>,
+    /// The time after which the enemy despawns on its own.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "secs_option")]
+    pub life_duration: Option<Duration>,
+    /// The effects the enemy spawns with.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub effects: Vec<ActionEffect>,
+}
+
+impl EnemyDefinition {
+    /// Constructs the template enemies described by this definition are spawned from.
+    pub fn to_template(&self) -> EntityTemplate {
+        let mut template = EntityTemplate::new()
+            .with_graphics(components::Graphics::new(
+                &self.sprite,
+                Duration::from_secs_f32(self.frame_time),
+            ))
+            .with_enemy(components::Enemy::new(self.damage, self.bounty, self.id))
+            .with_health(self.health)
+            .with_collision(components::Collision::new_basic(self.size.0, self.size.1));
+        if let Some(velocity) = self.velocity {
+            template = template.with_velocity_vec(velocity);
+        }
+        if let Some(boundary_collision) = self.boundary_collision {
+            template = template.with_boundary_collision(boundary_collision);
+        }
+        if let Some(offset) = self.offset {
+            template = template.with_offset(offset.x, offset.y);
+        }
+        if let Some(life_duration) = self.life_duration {
+            template = template.with_life_duration(life_duration);
+        }
+        for effect in &self.effects {
+            template = template.with_effect(effect.clone());
+        }
+        template
+    }
+
+    /// Constructs the descriptor of this enemy, as used by the [super::Director].
+    pub fn to_descriptor(&self, sprite_pool: &SpritePool) -> EnemyDescriptor {
+        EnemyDescriptor::new(
+            sprite_pool
+                .init_sprite(&self.sprite, Duration::ZERO)
+                .unwrap_or_default(),
+            &self.name,
+            &self.description,
+            self.cost,
+            self.to_template(),
+        )
+    }
+}
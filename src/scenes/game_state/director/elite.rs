@@ -0,0 +1,123 @@
+use ggez::glam::Vec2;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    components::{
+        actions::{ActionEffect, ActionEffectTarget, GameAction, RemoveSource},
+        graphics::Particle,
+        EntityTemplate,
+    },
+    GameRng,
+};
+
+/// The path of the file all elite affixes are defined in, relative to the data folder.
+pub const AFFIX_LIST_PATH: &str = "affixes.toml";
+
+/// The chance of a spawn being promoted to an elite gained per wave.
+const ELITE_CHANCE_PER_WAVE: f32 = 0.015;
+/// The maximum chance of a spawn being promoted to an elite.
+const MAX_ELITE_CHANCE: f32 = 0.25;
+/// Every this many waves, elites receive an additional affix.
+const WAVES_PER_AFFIX: u32 = 8;
+
+/// The factor the health of elites is scaled by.
+const ELITE_HEALTH_FACTOR: f32 = 3.;
+/// The factor the damage elites deal to the city is scaled by.
+const ELITE_DAMAGE_FACTOR: i32 = 2;
+/// The factor the bounty of elites is scaled by.
+const ELITE_BOUNTY_FACTOR: i32 = 4;
+
+/// The horizontal distance between the particles of multiple affixes.
+const PARTICLE_SPACING: f32 = 24.;
+
+/// An affix that can be added to elite enemies, granting them additional abilities.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EliteAffix {
+    /// The name of the affix.
+    pub name: String,
+    /// A short description of what the affix does.
+    pub description: String,
+    /// The particle displayed above elites with this affix.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    particle: Option<Particle>,
+    /// The effects added to elites with this affix.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    effects: Vec<ActionEffect>,
+    /// The amount of weaker copies elites with this affix split into on death.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    split: usize,
+}
+
+/// Helper function to skip saving affixes that don't split.
+fn is_zero(value: &usize) -> bool {
+    *value == 0
+}
+
+/// A list of all affixes, as saved in the affix list file.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+struct AffixList {
+    affix: Vec<EliteAffix>,
+}
+
+/// Loads all affixes from the affix list.
+pub(super) fn load_affixes() -> Vec<EliteAffix> {
+    crate::data::from_path::<AffixList>(crate::paths::data_path(AFFIX_LIST_PATH))
+        .map_err(|e| println!("[ERROR/Radish] Could not load elite affixes: {}", e))
+        .unwrap_or_default()
+        .affix
+}
+
+/// Rolls wether a spawn in the passed wave is promoted to an elite.
+pub(super) fn roll_elite(wave: u32, rng: &mut GameRng) -> bool {
+    rng.gen::<f32>() < (wave as f32 * ELITE_CHANCE_PER_WAVE).min(MAX_ELITE_CHANCE)
+}
+
+/// Creates the template of an elite version of the passed enemy template, with a wave-dependent amount of random affixes.
+pub(super) fn promote(
+    template: &EntityTemplate,
+    affixes: &[EliteAffix],
+    wave: u32,
+    rng: &mut GameRng,
+) -> EntityTemplate {
+    let amount = (1 + (wave / WAVES_PER_AFFIX) as usize).min(affixes.len());
+    let chosen = rand::seq::index::sample(rng, affixes.len(), amount);
+
+    let mut elite = template.clone().with_elite(
+        ELITE_HEALTH_FACTOR,
+        ELITE_DAMAGE_FACTOR,
+        ELITE_BOUNTY_FACTOR,
+    );
+
+    for (i, index) in chosen.iter().enumerate() {
+        let affix = &affixes[index];
+        if let Some(particle) = &affix.particle {
+            // spread the particles of multiple affixes horizontally
+            let offset = (i as f32 - (amount - 1) as f32 / 2.) * PARTICLE_SPACING;
+            elite = elite.with_particle(
+                particle
+                    .clone()
+                    .with_relative_position_offset(Vec2::new(offset, 0.)),
+            );
+        }
+        for effect in &affix.effects {
+            elite = elite.with_effect(effect.clone());
+        }
+        if affix.split > 0 {
+            elite = elite.with_effect(ActionEffect::on_death(
+                ActionEffectTarget::new_only_self(),
+                RemoveSource::HealthLoss,
+                GameAction::spawn(
+                    template
+                        .clone()
+                        .with_offset(0., 0.)
+                        .with_jitter(64., 32.)
+                        .with_count(affix.split)
+                        .with_health_factor(0.5),
+                ),
+            ));
+        }
+    }
+
+    elite
+}
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+/// The folder named presets of game configs are saved to and loaded from, relative to the save folder.
+pub const PRESET_FOLDER: &str = "presets";
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct GameConfig {
     // --- Director Config ---
@@ -27,9 +30,66 @@ pub struct GameConfig {
     pub achievements_unlocked: super::achievements::AchievementProgressSource,
 
     pub initial_camera_offset: f32,
+
+    /// The seed of the gameplay random number generator. If none is set, a random seed is chosen when the game starts.
+    #[serde(default)]
+    pub seed: Option<u64>,
+
+    /// The path of the campaign whose authored waves are played, relative to the data folder. If none is set, endless random waves are played.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub campaign: Option<String>,
+
+    /// The daily challenge this game plays, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily: Option<super::daily::DailyChallenge>,
+
+    /// The difficulty this game is played on.
+    #[serde(default)]
+    pub difficulty: super::difficulty::Difficulty,
+
+    /// The mutators active in this game.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mutators: Vec<super::mutators::Mutator>,
+
+    /// Wether this game was set up by the player or is a regular run.
+    #[serde(default)]
+    pub mode: RunMode,
+}
+
+/// The ways a run recorded in the highscore list can be set up.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RunMode {
+    /// A regular run, possibly with a difficulty and mutators.
+    #[default]
+    Endless,
+    /// A run with a config edited by the player, loaded from a preset or from a file.
+    Custom,
+}
+
+impl RunMode {
+    /// All run modes, in the order they are listed in.
+    pub const ALL: [Self; 2] = [Self::Endless, Self::Custom];
+
+    /// Returns the name of this run mode.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Endless => "Endless",
+            Self::Custom => "Custom",
+        }
+    }
+}
+
+impl crate::storage::SaveFile for GameConfig {
+    const VERSION: u32 = 1;
 }
 
 impl GameConfig {
+    /// Returns a random seed for the gameplay random number generator.
+    /// Seeds are kept below 2^32 so they stay short enough to share and fit into a toml integer.
+    pub fn random_seed() -> u64 {
+        rand::random::<u32>() as u64
+    }
+
     /// Constructs a default game config for debugging the game
     pub fn debug() -> Self {
         Self {
@@ -45,35 +105,96 @@ impl GameConfig {
             tutorial: true,
             achievements_unlocked: super::achievements::AchievementProgressSource::Percentage(1.),
             initial_camera_offset: 0.,
+            seed: None,
+            campaign: None,
+            daily: None,
+            difficulty: super::difficulty::Difficulty::Normal,
+            mutators: Vec::new(),
+            mode: RunMode::Custom,
         }
     }
 
-    #[allow(dead_code)]
+    /// Activates a mutator in this config and returns it builder-pattern style.
+    pub fn with_mutator(mut self, mutator: super::mutators::Mutator) -> Self {
+        if !self.mutators.contains(&mutator) {
+            mutator.apply(&mut self);
+            self.mutators.push(mutator);
+        }
+        self
+    }
+
+    /// Sets the difficulty of this config, scaling the values it affects, and returns it builder-pattern style.
+    /// The values are scaled from their current state, so this should only be called once per config.
+    pub fn with_difficulty(mut self, difficulty: super::difficulty::Difficulty) -> Self {
+        difficulty.apply(&mut self);
+        self.difficulty = difficulty;
+        self
+    }
+
+    /// Constructs a game config playing the campaign from the main menu.
+    pub fn campaign() -> Self {
+        Self {
+            campaign: Some(super::director::CAMPAIGN_PATH.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    /// Constructs a game config playing the daily challenge of the current day.
+    pub fn daily() -> Self {
+        super::daily::DailyChallenge::today().to_config()
+    }
+
     /// Loads a game config from the given path and constructs a controller.
     pub fn from_path(
         path: impl AsRef<std::path::Path>,
     ) -> Result<Self, Box<dyn std::error::Error>> {
-        let string = std::fs::read_to_string(
-            path.as_ref()
-                .to_str()
-                .ok_or_else(|| ggez::GameError::CustomError("Could not read path.".to_owned()))?,
-        )?;
-        Ok(toml::from_str(&string)?)
+        crate::storage::read(path)
     }
 
-    #[allow(dead_code)]
     /// Saves this game config to the given path.
     pub fn save_to_file(
         &self,
         path: impl AsRef<std::path::Path>,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        std::fs::write(
-            path.as_ref()
-                .to_str()
-                .ok_or_else(|| ggez::GameError::CustomError("Could not read path.".to_owned()))?,
-            toml::to_string(&self)?,
-        )?;
-        Ok(())
+        crate::storage::save(path, self)
+    }
+
+    /// Loads all presets from the preset folder, ordered by name.
+    /// Presets that fail to load are reported and skipped.
+    pub fn load_presets() -> Vec<(String, Self)> {
+        let mut paths: Vec<std::path::PathBuf> =
+            std::fs::read_dir(crate::paths::save_path(PRESET_FOLDER))
+                .map(|dir| {
+                    dir.filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                        .filter(|path| path.extension().map(|ext| ext == "toml").unwrap_or(false))
+                        .collect()
+                })
+                .unwrap_or_default();
+        paths.sort();
+
+        paths
+            .iter()
+            .filter_map(|path| {
+                let name = path.file_stem()?.to_string_lossy().into_owned();
+                match Self::from_path(path) {
+                    Ok(config) => Some((name, config)),
+                    Err(e) => {
+                        println!(
+                            "[ERROR/Radish] Could not load preset {}: {}",
+                            path.display(),
+                            e
+                        );
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Saves this game config as a preset with the passed name to the preset folder, replacing any preset of the same name.
+    pub fn save_preset(&self, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+        std::fs::create_dir_all(crate::paths::save_path(PRESET_FOLDER))?;
+        self.save_to_file(crate::paths::save_path(PRESET_FOLDER).join(format!("{}.toml", name)))
     }
 }
 
@@ -92,6 +213,12 @@ impl Default for GameConfig {
             tutorial: true,
             achievements_unlocked: super::achievements::AchievementProgressSource::Cache,
             initial_camera_offset: 1500.,
+            seed: None,
+            campaign: None,
+            daily: None,
+            difficulty: super::difficulty::Difficulty::Normal,
+            mutators: Vec::new(),
+            mode: RunMode::Endless,
         }
     }
 }
@@ -1,21 +1,38 @@
 use super::{
     components::{actions::GameAction, buildings::Buildings},
-    game_message::{GameMessage, MessageSet},
+    game_message::{GameMessage, MessageQueue},
 };
 use legion::*;
+use serde::{Deserialize, Serialize};
 
 /// A struct that holds some general data about the game play.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct GameData {
     /// The current total score achieved.
     score: i32,
     /// The gold the player currently holds (= score - gold spent).
     gold: i32,
     /// The gold the player had on the last pass (may have changed from the outside)
+    #[serde(skip)]
     last_gold: i32,
+    /// The gold spent since the last pass, to be reported to listeners.
+    #[serde(skip)]
+    spent: i32,
+    /// The gold earned since the last pass, to be reported to listeners.
+    #[serde(skip)]
+    earned: i32,
     /// The health the city has left. Public to allow easy access.
     pub city_health: i32,
     /// The current state of buildings
     pub buildings: Buildings,
+    /// The factor the final score is multiplied with, granted by mutators.
+    #[serde(default = "default_score_factor")]
+    score_factor: f32,
+}
+
+/// Helper function for saves from before score factors were introduced.
+fn default_score_factor() -> f32 {
+    1.
 }
 
 impl GameData {
@@ -25,15 +42,25 @@ impl GameData {
             score: 0,
             gold,
             last_gold: 0,
+            spent: 0,
+            earned: 0,
             city_health,
             buildings: Buildings::new(),
+            score_factor: 1.,
         }
     }
 
+    /// Sets the factor the final score is multiplied with and returns the game data builder-pattern style.
+    pub fn with_score_factor(mut self, score_factor: f32) -> Self {
+        self.score_factor = score_factor;
+        self
+    }
+
     /// Adds both gold and score to the player.
     pub fn add_gold(&mut self, amount: i32) {
         self.score += amount;
         self.gold += amount;
+        self.earned += amount;
     }
 
     /// Attempts to spend a certain amount of gold.
@@ -42,15 +69,16 @@ impl GameData {
     pub fn spend(&mut self, amount: i32) -> bool {
         if amount <= self.gold {
             self.gold -= amount;
+            self.spent += amount;
             true
         } else {
             false
         }
     }
 
-    /// Returns the current score.
+    /// Returns the current score, multiplied with the score factor.
     pub fn get_score(&self) -> i32 {
-        self.score
+        (self.score as f32 * self.score_factor) as i32
     }
 }
 
@@ -59,7 +87,7 @@ impl GameData {
 pub fn resolve_gama_data(
     actions: &super::components::Actions,
     #[resource] game_data: &mut GameData,
-    #[resource] messages: &mut MessageSet,
+    #[resource] messages: &mut MessageQueue,
 ) {
     let mut change_city = false;
     for action in actions.get_actions() {
@@ -71,19 +99,37 @@ pub fn resolve_gama_data(
                 game_data.city_health -= *dmg;
                 change_city = true;
             }
+            GameAction::RepairCity { amount } => {
+                game_data.city_health += *amount;
+                change_city = true;
+            }
             _ => {}
         }
     }
 
     if game_data.last_gold != game_data.gold {
-        messages.insert(mooeye::ui::UiMessage::Extern(GameMessage::UpdateGold(
+        messages.push(mooeye::ui::UiMessage::Extern(GameMessage::UpdateGold(
             game_data.gold,
         )));
         game_data.last_gold = game_data.gold;
     }
 
+    if game_data.spent > 0 {
+        messages.push(mooeye::ui::UiMessage::Extern(GameMessage::GoldSpent(
+            game_data.spent,
+        )));
+        game_data.spent = 0;
+    }
+
+    if game_data.earned > 0 {
+        messages.push(mooeye::ui::UiMessage::Extern(GameMessage::GoldEarned(
+            game_data.earned,
+        )));
+        game_data.earned = 0;
+    }
+
     if change_city {
-        messages.insert(mooeye::ui::UiMessage::Extern(
+        messages.push(mooeye::ui::UiMessage::Extern(
             GameMessage::UpdateCityHealth(game_data.city_health),
         ));
     }
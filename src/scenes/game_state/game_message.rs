@@ -1,56 +1,226 @@
 use mooeye::ui;
+use serde::{Deserialize, Serialize};
 
+/// An event happening in the game, to be reacted to by the UI, achievements and the tutorial.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, std::hash::Hash)]
 pub enum GameMessage {
     UpdateCityHealth(i32),
     UpdateGold(i32),
-    // needs to use u8 instead of f32 to allow ordering
+    // needs to use u8 instead of f32 to allow hashing
     UpdateSpellSlots(usize, u8),
     NextWave(i32),
-    EnemyKilled(u8),
-    EliteKilled,
-    BuildingUp(usize, u8),
-    BuildingDown(usize, u8),
+    /// An enemy was killed.
+    EnemyKilled {
+        /// The identifier of the kind of enemy killed.
+        enemy: u8,
+        /// Wether the killed enemy was an elite.
+        elite: bool,
+        /// The spell that dealt the killing blow, if any.
+        spell: Option<u8>,
+        /// The horizontal position the enemy died at.
+        x: i32,
+        /// The vertical position the enemy died at.
+        y: i32,
+        /// The gold granted for the kill.
+        bounty: i32,
+    },
+    /// An enemy took damage.
+    DamageDealt {
+        /// The identifier of the kind of enemy damaged.
+        enemy: u8,
+        /// The spell that dealt the damage, if any.
+        spell: Option<u8>,
+        /// The amount of damage dealt.
+        amount: i32,
+    },
+    /// An enemy reached the city and damaged it.
+    CityDamaged {
+        /// The identifier of the kind of enemy.
+        enemy: u8,
+        /// The amount of damage dealt to the city.
+        amount: i32,
+    },
+    /// A spell was cast.
+    SpellCast {
+        /// The identifier of the spell cast.
+        spell: u8,
+    },
+    /// Gold was spent in the wave menu.
+    GoldSpent(i32),
+    /// Gold was earned, such as by killing enemies.
+    GoldEarned(i32),
+    /// A building was constructed or upgraded.
+    BuildingUp {
+        /// The index of the building.
+        building: usize,
+        /// The level the building was upgraded to.
+        level: u8,
+    },
+    /// A building was destroyed by an enemy.
+    BuildingLost {
+        /// The index of the building.
+        building: usize,
+    },
+    /// A boss entered the battlefield.
+    BossSpawned {
+        /// The index of the boss within the list of bosses.
+        boss: u8,
+    },
+    /// The health of a boss changed.
+    BossHealth {
+        /// The index of the boss within the list of bosses.
+        boss: u8,
+        /// The current health of the boss.
+        health: i32,
+        /// The maximum health of the boss.
+        max_health: i32,
+    },
+    /// A boss left the battlefield.
+    BossRemoved {
+        /// The index of the boss within the list of bosses.
+        boss: u8,
+        /// Wether the boss was killed, as opposed to reaching the town.
+        killed: bool,
+    },
+    /// The last wave of a campaign was beaten.
+    CampaignWon,
 }
 
-impl PartialOrd for GameMessage {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        match (self, other) {
-            (Self::UpdateCityHealth(a), Self::UpdateCityHealth(b)) => Some(a.cmp(b)),
-            (Self::UpdateGold(a), Self::UpdateGold(b)) => Some(a.cmp(b)),
-            (Self::UpdateSpellSlots(_, a), Self::UpdateSpellSlots(_, b)) => Some(a.cmp(b)),
-            (Self::NextWave(a), Self::NextWave(b)) => Some(a.cmp(b)),
-            (Self::EnemyKilled(a), Self::EnemyKilled(b)) => Some(a.cmp(b)),
-            (Self::EliteKilled, Self::EliteKilled) => Some(std::cmp::Ordering::Equal),
-            (Self::BuildingUp(a, lvl_a), Self::BuildingUp(b, lvl_b)) => {
-                if *a == *b {
-                    Some(lvl_a.cmp(lvl_b))
-                } else {
-                    None
-                }
-            }
-            (Self::BuildingDown(a, _), Self::BuildingDown(b, _)) => Some(a.cmp(b)),
-            (_, _) => None,
-        }
-    }
+/// A condition on [GameMessage]s. Every field left empty matches any value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameMessagePredicate {
+    /// Matches any change of the city health.
+    CityHealth,
+    /// Matches any change of the players gold.
+    Gold,
+    /// Matches the start of a wave.
+    NextWave {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        wave: Option<i32>,
+    },
+    /// Matches enemy kills.
+    EnemyKilled {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        enemy: Option<u8>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        elite: Option<bool>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        spell: Option<u8>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_bounty: Option<i32>,
+    },
+    /// Matches damage dealt to enemies.
+    DamageDealt {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        enemy: Option<u8>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        spell: Option<u8>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_amount: Option<i32>,
+    },
+    /// Matches spell casts.
+    SpellCast {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        spell: Option<u8>,
+    },
+    /// Matches spending gold.
+    GoldSpent {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_amount: Option<i32>,
+    },
+    /// Matches constructing or upgrading buildings.
+    BuildingUp {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        building: Option<usize>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_level: Option<u8>,
+    },
+    /// Matches losing buildings.
+    BuildingLost {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        building: Option<usize>,
+    },
+    /// Matches bosses leaving the battlefield.
+    BossRemoved {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        boss: Option<u8>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        killed: Option<bool>,
+    },
+    /// Matches winning a campaign.
+    CampaignWon,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Default)]
-pub enum GameMessageFilter {
-    Equality,
-    Max,
-    Min,
-    #[default]
-    Type,
+/// Returns true if the condition is empty or equal to the value.
+fn field_eq<T: PartialEq>(condition: &Option<T>, value: &T) -> bool {
+    condition.as_ref().map(|c| c == value).unwrap_or(true)
 }
 
-impl GameMessageFilter {
-    pub fn check(&self, model: &GameMessage, to_check: &GameMessage) -> bool {
-        match self {
-            GameMessageFilter::Equality => model == to_check,
-            GameMessageFilter::Max => model >= to_check,
-            GameMessageFilter::Min => model <= to_check,
-            GameMessageFilter::Type => model <= to_check || model >= to_check,
+/// Returns true if the condition is empty or at most the value.
+fn field_min<T: PartialOrd>(condition: &Option<T>, value: &T) -> bool {
+    condition.as_ref().map(|c| c <= value).unwrap_or(true)
+}
+
+impl GameMessagePredicate {
+    /// Returns wether the passed message satisfies this predicate.
+    pub fn matches(&self, message: &GameMessage) -> bool {
+        match (self, message) {
+            (Self::CityHealth, GameMessage::UpdateCityHealth(_)) => true,
+            (Self::Gold, GameMessage::UpdateGold(_)) => true,
+            (Self::NextWave { wave }, GameMessage::NextWave(w)) => field_eq(wave, w),
+            (
+                Self::EnemyKilled {
+                    enemy,
+                    elite,
+                    spell,
+                    min_bounty,
+                },
+                GameMessage::EnemyKilled {
+                    enemy: e,
+                    elite: el,
+                    spell: s,
+                    bounty: b,
+                    ..
+                },
+            ) => {
+                field_eq(enemy, e)
+                    && field_eq(elite, el)
+                    && (spell.is_none() || spell == s)
+                    && field_min(min_bounty, b)
+            }
+            (
+                Self::DamageDealt {
+                    enemy,
+                    spell,
+                    min_amount,
+                },
+                GameMessage::DamageDealt {
+                    enemy: e,
+                    spell: s,
+                    amount: a,
+                },
+            ) => field_eq(enemy, e) && (spell.is_none() || spell == s) && field_min(min_amount, a),
+            (Self::SpellCast { spell }, GameMessage::SpellCast { spell: s }) => field_eq(spell, s),
+            (Self::GoldSpent { min_amount }, GameMessage::GoldSpent(a)) => field_min(min_amount, a),
+            (
+                Self::BuildingUp {
+                    building,
+                    min_level,
+                },
+                GameMessage::BuildingUp {
+                    building: b,
+                    level: l,
+                },
+            ) => field_eq(building, b) && field_min(min_level, l),
+            (Self::BuildingLost { building }, GameMessage::BuildingLost { building: b }) => {
+                field_eq(building, b)
+            }
+            (
+                Self::BossRemoved { boss, killed },
+                GameMessage::BossRemoved { boss: b, killed: k },
+            ) => field_eq(boss, b) && field_eq(killed, k),
+            (Self::CampaignWon, GameMessage::CampaignWon) => true,
+            _ => false,
         }
     }
 }
@@ -58,22 +228,24 @@ impl GameMessageFilter {
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum UiMessageFilter {
     Ui(ui::UiMessage<GameMessage>),
-    Ext(GameMessage, GameMessageFilter),
+    Ext(GameMessagePredicate),
 }
 
 impl UiMessageFilter {
     pub fn check(&self, to_check: &ui::UiMessage<GameMessage>) -> bool {
         match self {
             UiMessageFilter::Ui(model) => model == to_check,
-            UiMessageFilter::Ext(model, filter) => match to_check {
-                ui::UiMessage::Extern(content) => filter.check(model, content),
+            UiMessageFilter::Ext(predicate) => match to_check {
+                ui::UiMessage::Extern(content) => predicate.matches(content),
                 _ => false,
             },
         }
     }
 }
 
-pub type MessageSet = std::collections::HashSet<ui::UiMessage<GameMessage>>;
+/// An ordered queue of messages. Unlike the message sets used by the GUI, every occurrence of a message is kept,
+/// so listeners counting messages (such as kill-count achievements) see all of them.
+pub type MessageQueue = Vec<ui::UiMessage<GameMessage>>;
 
 pub trait MessageReceiver {
     fn receive(
@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    components::{
+        actions::{ActionEffect, ActionEffectTarget, ActionTransform},
+        spell::SpellPool,
+    },
+    game_data::GameData,
+    GameConfig,
+};
+
+/// A mutator altering the rules of a run in exchange for a higher score.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Mutator {
+    /// All enemies move faster.
+    Frenzy,
+    /// All spells block twice as many spell slots.
+    Overcharge,
+    /// No buildings can be constructed.
+    NoBuildings,
+    /// The city falls on the first hit.
+    GlassCity,
+    /// Every spawned enemy is an elite.
+    EliteHorde,
+}
+
+impl Mutator {
+    /// All mutators, in the order they are displayed in.
+    pub const ALL: [Self; 5] = [
+        Self::Frenzy,
+        Self::Overcharge,
+        Self::NoBuildings,
+        Self::GlassCity,
+        Self::EliteHorde,
+    ];
+
+    /// Returns the name of this mutator.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Frenzy => "Frenzy",
+            Self::Overcharge => "Overcharge",
+            Self::NoBuildings => "No Buildings",
+            Self::GlassCity => "Glass City",
+            Self::EliteHorde => "Elite Horde",
+        }
+    }
+
+    /// Returns a short description of this mutator.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Frenzy => "Enemies move 50% faster.",
+            Self::Overcharge => "Spells block twice as many spell slots, or block them longer.",
+            Self::NoBuildings => "No buildings can be constructed.",
+            Self::GlassCity => "The city falls on the first hit.",
+            Self::EliteHorde => "Every enemy is an elite.",
+        }
+    }
+
+    /// Returns the factor the final score is multiplied with while this mutator is active.
+    pub fn score_factor(&self) -> f32 {
+        match self {
+            Self::Frenzy => 1.5,
+            Self::Overcharge => 1.5,
+            Self::NoBuildings => 1.25,
+            Self::GlassCity => 2.,
+            Self::EliteHorde => 2.,
+        }
+    }
+
+    /// Applies the changes of this mutator to the fields of the passed config.
+    pub fn apply(&self, config: &mut GameConfig) {
+        if *self == Self::GlassCity {
+            config.starting_city_health = 1;
+        }
+    }
+
+    /// Returns the effects this mutator applies to every enemy in the world.
+    pub fn global_effects(&self) -> Vec<ActionEffect> {
+        match self {
+            Self::Frenzy => vec![ActionEffect::transform(
+                ActionEffectTarget::new().with_enemies_only(true),
+                ActionTransform::ScaleMove { x: 1.5, y: 1.5 },
+            )],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Applies the changes of this mutator to the game data of a new run.
+    pub fn apply_to_game_data(&self, game_data: &mut GameData) {
+        if *self == Self::NoBuildings {
+            game_data.buildings.lock();
+        }
+    }
+
+    /// Applies the changes of this mutator to the spells of the passed spell pool.
+    pub fn apply_to_spell_pool(&self, spell_pool: &mut SpellPool) {
+        if *self == Self::Overcharge {
+            for template in spell_pool.1.iter_mut() {
+                template.spell.scale_slot_cost(2);
+            }
+        }
+    }
+}
+
+/// Returns the factor the final score is multiplied with while all passed mutators are active.
+pub fn score_factor(mutators: &[Mutator]) -> f32 {
+    mutators
+        .iter()
+        .map(|mutator| mutator.score_factor())
+        .product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenes::game_state::{achievements, components::spell};
+
+    #[test]
+    fn all_spells_castable_with_mutators() {
+        let achievements = achievements::AchievementSet::load(
+            None,
+            achievements::AchievementProgressSource::Percentage(1.),
+        );
+        let mut spell_pool =
+            spell::init_spell_pool(&mooeye::sprite::SpritePool::new(), &achievements);
+        for mutator in Mutator::ALL {
+            mutator.apply_to_spell_pool(&mut spell_pool);
+        }
+        for template in &spell_pool.1 {
+            assert!(
+                template.spell.get_slot_cost() <= spell::MAX_SPELL_SLOTS,
+                "{} blocks {} spell slots",
+                template.spell.get_name(),
+                template.spell.get_slot_cost()
+            );
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use std::{collections::HashMap, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use super::{controller::Command, GameConfig, Interactions};
+
+/// The folder all replays are saved to, relative to the save folder.
+const REPLAY_FOLDER: &str = "replays";
+/// The amount of replays kept in the replay folder. Older replays are deleted when a new one is saved.
+const REPLAYS_KEPT: usize = 20;
+
+/// A recording of all inputs of a single run.
+/// As all gameplay randomness is seeded from the config, this is enough to reproduce the run exactly.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Replay {
+    /// The seed of the recorded run.
+    pub seed: u64,
+    /// The config the recorded run was started with.
+    pub config: GameConfig,
+    /// The inputs of every frame of the run.
+    frames: Vec<ReplayFrame>,
+}
+
+/// The inputs of a single frame of a recorded run.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+struct ReplayFrame {
+    /// The time passed in this frame in nanoseconds. None if the simulation was paused for this frame.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    delta: Option<u64>,
+    /// The commands issued by the player in this frame.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    commands: Vec<Command>,
+    /// The ids of all UI elements triggered in this frame, e.g. by purchases in the wave menu.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    triggers: Vec<u32>,
+}
+
+impl crate::storage::SaveFile for Replay {
+    const VERSION: u32 = 1;
+}
+
+impl Replay {
+    /// Creates a new, empty replay for a run started with the passed config.
+    /// The config's seed should already be set, otherwise a seed is chosen now.
+    pub fn new(mut config: GameConfig) -> Self {
+        let seed = config.seed.unwrap_or_else(GameConfig::random_seed);
+        config.seed = Some(seed);
+        Self {
+            seed,
+            config,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Loads a replay from the given path.
+    pub fn from_path(
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        crate::storage::read(path)
+    }
+
+    /// Saves this replay to the given path.
+    pub fn save_to_file(
+        &self,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        crate::storage::save(path, self)
+    }
+
+    /// Loads the most recently saved replay from the replay folder, if there is one.
+    pub fn latest() -> Option<Self> {
+        Self::list_folder()
+            .last()
+            .and_then(|path| Self::from_path(path).ok())
+    }
+
+    /// Returns the paths of all replays in the replay folder, oldest first.
+    fn list_folder() -> Vec<std::path::PathBuf> {
+        let mut paths: Vec<std::path::PathBuf> =
+            std::fs::read_dir(crate::paths::save_path(REPLAY_FOLDER))
+                .map(|dir| {
+                    dir.filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                        .filter(|path| path.extension().map(|ext| ext == "toml").unwrap_or(false))
+                        .collect()
+                })
+                .unwrap_or_default();
+        paths.sort();
+        paths
+    }
+
+    /// Saves this replay into the replay folder, named by the current time, and removes the oldest replays if there are too many.
+    /// Names are padded to a fixed width, so they sort in the order the replays were saved.
+    fn save_to_folder(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let folder = crate::paths::save_path(REPLAY_FOLDER);
+        std::fs::create_dir_all(&folder)?;
+        let time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default();
+        // never overwrite a replay saved at the same time
+        let mut nanos = time.subsec_nanos();
+        let path = loop {
+            let path = folder.join(format!("replay_{:012}_{:09}.toml", time.as_secs(), nanos));
+            if !path.exists() {
+                break path;
+            }
+            nanos += 1;
+        };
+        self.save_to_file(path)?;
+
+        let paths = Self::list_folder();
+        for path in paths.iter().take(paths.len().saturating_sub(REPLAYS_KEPT)) {
+            std::fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Records the inputs of a running game into a replay and saves it to the replay folder once dropped.
+pub struct ReplayRecorder {
+    replay: Replay,
+}
+
+impl ReplayRecorder {
+    /// Creates a new recorder for a run started with the passed config.
+    pub fn new(config: GameConfig) -> Self {
+        Self {
+            replay: Replay::new(config),
+        }
+    }
+
+    /// Records a frame, consisting of the interactions the simulation was stepped with (if it was stepped) and all triggered UI elements.
+    pub fn record(&mut self, interactions: Option<&Interactions>, triggers: Vec<u32>) {
+        self.replay.frames.push(ReplayFrame {
+            delta: interactions.map(|ix| ix.delta.as_nanos() as u64),
+            commands: interactions
+                .map(|ix| {
+                    ix.commands
+                        .iter()
+                        .filter(|(_, &active)| active)
+                        .map(|(&command, _)| command)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            triggers,
+        });
+    }
+}
+
+impl Drop for ReplayRecorder {
+    fn drop(&mut self) {
+        // don't save runs that never started
+        if self.replay.frames.iter().any(|frame| frame.delta.is_some())
+            && self.replay.save_to_folder().is_err()
+        {
+            println!("[ERROR/Radish] Could not save replay.");
+        }
+    }
+}
+
+/// Feeds the inputs of a replay back into a running game, frame by frame.
+pub struct ReplayPlayer {
+    replay: Replay,
+    /// The index of the frame currently played.
+    current: Option<usize>,
+}
+
+impl ReplayPlayer {
+    /// Creates a new player starting before the first frame of the passed replay.
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            current: None,
+        }
+    }
+
+    /// Advances to the next frame and returns the interactions to step the simulation with, if it was stepped in that frame.
+    /// Once the replay has run out, no more interactions are returned.
+    pub fn next_frame(&mut self) -> Option<Interactions> {
+        let next = self.current.map(|i| i + 1).unwrap_or_default();
+        self.current = Some(next);
+        self.replay.frames.get(next).and_then(|frame| {
+            frame.delta.map(|delta| Interactions {
+                commands: frame
+                    .commands
+                    .iter()
+                    .map(|&command| (command, true))
+                    .collect::<HashMap<Command, bool>>(),
+                delta: Duration::from_nanos(delta),
+            })
+        })
+    }
+
+    /// Returns the ids of all UI elements triggered in the current frame.
+    pub fn triggers(&self) -> &[u32] {
+        self.current
+            .and_then(|i| self.replay.frames.get(i))
+            .map(|frame| frame.triggers.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+/// Describes wether a game records its inputs or plays back those of a replay.
+pub enum ReplayMode {
+    /// Inputs are read from the controller and recorded.
+    Recording(ReplayRecorder),
+    /// Inputs are read from a replay.
+    Playback(ReplayPlayer),
+    /// Inputs are read from the controller, but not recorded, as the game was resumed from a save game.
+    Unrecorded,
+}
@@ -0,0 +1,209 @@
+use std::collections::{BTreeMap, HashMap};
+
+use mooeye::ui as mui;
+use serde::{Deserialize, Serialize};
+
+use super::{GameMessage, MessageQueue};
+
+/// Statistics about a run, kept as a resource of the simulation and saved along with it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunStats {
+    /// The seed the random number generator of the run was started with.
+    pub seed: u64,
+    /// The time simulated so far, in seconds.
+    pub duration: f32,
+    /// The amount of enemies killed, including elites.
+    pub kills: u32,
+    /// The amount of elite enemies killed.
+    pub elite_kills: u32,
+    /// The amount of bosses killed.
+    pub boss_kills: u32,
+    /// The amount of waves cleared.
+    #[serde(default)]
+    pub waves: u32,
+    /// The gold earned, before the score factor is applied.
+    #[serde(default)]
+    pub gold_earned: u32,
+    /// The gold spent in the wave menu.
+    #[serde(default)]
+    pub gold_spent: u32,
+    /// The amount of enemies killed, by enemy identifier.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub enemy_kills: Vec<u32>,
+    /// The amount of casts, by spell identifier.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spell_casts: Vec<u32>,
+    /// The damage dealt to enemies, by spell identifier.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spell_damage: Vec<u32>,
+    /// The amount of times each building was constructed, by building index.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub buildings_built: Vec<u32>,
+    /// The amount of times each building was destroyed, by building index.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub buildings_lost: Vec<u32>,
+    /// The damage dealt to the city, by enemy identifier.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub city_damage: Vec<u32>,
+    /// The gold earned during each wave cleared.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub wave_gold: Vec<u32>,
+    /// The last wave started, so a wave announced more than once is only counted once.
+    #[serde(default)]
+    last_wave: i32,
+    /// The current level of each building. Games saved before building levels were saved construct their buildings again when resumed, which must not count as building them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    building_levels: Vec<u8>,
+}
+
+impl RunStats {
+    /// Creates empty statistics for a run started with the passed seed.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            ..Default::default()
+        }
+    }
+
+    /// Counts kills, casts, damage, gold, buildings, city damage and waves among the passed messages.
+    pub fn receive(&mut self, messages: &MessageQueue) {
+        for message in messages {
+            let mui::UiMessage::Extern(message) = message else {
+                continue;
+            };
+            match *message {
+                GameMessage::EnemyKilled { enemy, elite, .. } => {
+                    self.kills += 1;
+                    if elite {
+                        self.elite_kills += 1;
+                    }
+                    tally(&mut self.enemy_kills, enemy as usize, 1);
+                }
+                GameMessage::BossRemoved { killed: true, .. } => {
+                    self.boss_kills += 1;
+                }
+                GameMessage::SpellCast { spell } => {
+                    tally(&mut self.spell_casts, spell as usize, 1);
+                }
+                GameMessage::DamageDealt {
+                    spell: Some(spell),
+                    amount,
+                    ..
+                } => {
+                    tally(&mut self.spell_damage, spell as usize, amount.max(0) as u32);
+                }
+                GameMessage::GoldEarned(amount) => {
+                    self.gold_earned += amount.max(0) as u32;
+                }
+                GameMessage::GoldSpent(amount) => {
+                    self.gold_spent += amount.max(0) as u32;
+                }
+                GameMessage::NextWave(wave) => {
+                    // the first wave announced is the one the run starts with
+                    if self.last_wave > 0 && wave > self.last_wave {
+                        self.waves += (wave - self.last_wave) as u32;
+                        self.wave_gold
+                            .push(self.gold_earned - self.wave_gold.iter().sum::<u32>());
+                    }
+                    self.last_wave = self.last_wave.max(wave);
+                }
+                GameMessage::BuildingUp { building, level } => {
+                    if self.building_levels.len() <= building {
+                        self.building_levels.resize(building + 1, 0);
+                    }
+                    if self.building_levels[building] == 0 {
+                        tally(&mut self.buildings_built, building, 1);
+                    }
+                    self.building_levels[building] = level;
+                }
+                GameMessage::CityDamaged { enemy, amount } => {
+                    tally(&mut self.city_damage, enemy as usize, amount.max(0) as u32);
+                }
+                GameMessage::BuildingLost { building } => {
+                    tally(&mut self.buildings_lost, building, 1);
+                    if let Some(level) = self.building_levels.get_mut(building) {
+                        *level = 0;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns the duration of the run formatted as minutes and seconds.
+    pub fn format_duration(&self) -> String {
+        let seconds = self.duration as u32;
+        format!("{}:{:02}", seconds / 60, seconds % 60)
+    }
+}
+
+/// The names of the enemies, spells and buildings that run statistics refer to by identifier, as currently defined in the data folder.
+pub struct StatNames {
+    /// The names of all enemies and bosses, by identifier.
+    enemies: HashMap<u8, String>,
+    /// The names of all spells, ordered by their identifier minus one.
+    spells: Vec<String>,
+}
+
+impl StatNames {
+    /// Loads the names of all enemies, spells and buildings from the data folder.
+    pub fn load() -> Self {
+        Self {
+            enemies: super::enemy_names(),
+            spells: super::spell_names(),
+        }
+    }
+
+    /// Returns the name of the enemy with the passed identifier.
+    pub fn enemy(&self, id: usize) -> Option<String> {
+        u8::try_from(id)
+            .ok()
+            .and_then(|id| self.enemies.get(&id))
+            .cloned()
+    }
+
+    /// Returns the name of the spell with the passed identifier.
+    pub fn spell(&self, id: usize) -> Option<String> {
+        id.checked_sub(1)
+            .and_then(|index| self.spells.get(index))
+            .cloned()
+    }
+
+    /// Returns the identifier of the spell with the passed name.
+    pub fn spell_id(&self, name: &str) -> Option<usize> {
+        self.spells
+            .iter()
+            .position(|spell| spell == name)
+            .map(|index| index + 1)
+    }
+
+    /// Returns the name of the building with the passed index.
+    pub fn building(&self, index: usize) -> Option<String> {
+        super::components::buildings::get_building_list()
+            .get(index)
+            .map(|info| info.name.clone())
+    }
+}
+
+/// Returns the non-zero counters of the passed list, indexed by identifier, keyed by the name of their identifier instead.
+/// Identifiers without a name are skipped.
+pub fn by_name(counters: &[u32], name: impl Fn(usize) -> Option<String>) -> BTreeMap<String, u64> {
+    let mut named = BTreeMap::new();
+    for (id, &count) in counters.iter().enumerate() {
+        if count == 0 {
+            continue;
+        }
+        if let Some(name) = name(id) {
+            *named.entry(name).or_default() += count as u64;
+        }
+    }
+    named
+}
+
+/// Adds the passed amount to the counter at the passed index, growing the list of counters as needed.
+fn tally(counters: &mut Vec<u32>, index: usize, amount: u32) {
+    if counters.len() <= index {
+        counters.resize(index + 1, 0);
+    }
+    counters[index] += amount;
+}
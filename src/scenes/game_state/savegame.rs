@@ -0,0 +1,303 @@
+use std::{collections::HashMap, time::Duration};
+
+use legion::{Entity, EntityStore, IntoQuery, Resources, World};
+use mooeye::sprite;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+use super::{components, director, game_data, run_stats::RunStats, GameConfig, GameRng};
+
+/// The path the current run is saved to when quitting mid-game, relative to the save folder.
+pub const SAVEGAME_PATH: &str = "savegame.toml";
+
+/// The complete state of a run, saved when quitting mid-game so the run can be resumed later.
+#[derive(Serialize, Deserialize)]
+pub struct SaveGame {
+    /// The config the saved run was started with.
+    pub config: GameConfig,
+    /// The seed the saved run was started with.
+    /// Saves without the state of the random number generator resume with a generator seeded from this instead.
+    pub seed: u64,
+    /// The state of the random number generator, so the resumed run continues the random numbers of the saved one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rng: Option<SavedRng>,
+    /// The score, gold, city health and buildings of the saved run.
+    pub(super) game_data: game_data::GameData,
+    /// The director of the saved run, containing the current wave.
+    pub(super) director: director::Director,
+    /// The levels of all spells in the spell pool, marking which spells have been purchased.
+    pub(super) spell_levels: Vec<u32>,
+    /// The statistics of the saved run so far.
+    #[serde(default)]
+    pub(super) stats: RunStats,
+    /// All entities of the saved world.
+    entities: Vec<SavedEntity>,
+}
+
+/// A single saved entity, consisting of all its saveable components.
+#[derive(Serialize, Deserialize, Default)]
+struct SavedEntity {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    position: Option<components::Position>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    velocity: Option<components::Velocity>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    graphics: Option<components::Graphics>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    health: Option<components::Health>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    enemy: Option<components::Enemy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    life_duration: Option<components::LifeDuration>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    collision: Option<components::Collision>,
+    /// The indices of all saved entities the collision of this entity is immune to.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    immunity: Vec<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    boundary_collision: Option<components::BoundaryCollision>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    actions: Option<components::Actions>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    control: Option<components::Control>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    spell_origin: Option<components::SpellOrigin>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    boss: Option<components::Boss>,
+    /// The equipped spells of a spell caster, as indices into the spell pool.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    spells: Option<Vec<usize>>,
+    /// The remaining and total blockage of all spell slots of a spell caster, including those granted by buildings.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    spell_slots: Vec<(Duration, Duration)>,
+}
+
+/// The state of a [GameRng], as saved in a save game.
+#[derive(Serialize, Deserialize)]
+struct SavedRng {
+    seed: [u8; 32],
+    stream: u64,
+    /// The position within the stream. Saved as text, as it does not fit into the integers of a save file.
+    word_pos: String,
+}
+
+impl SavedRng {
+    /// Saves the state of the passed generator.
+    fn new(rng: &GameRng) -> Self {
+        Self {
+            seed: rng.get_seed(),
+            stream: rng.get_stream(),
+            word_pos: rng.get_word_pos().to_string(),
+        }
+    }
+
+    /// Returns a generator continuing where the saved one left off, if the state is valid.
+    fn restore(&self) -> Option<GameRng> {
+        let mut rng = GameRng::from_seed(self.seed);
+        rng.set_stream(self.stream);
+        rng.set_word_pos(self.word_pos.parse().ok()?);
+        Some(rng)
+    }
+}
+
+impl crate::storage::SaveFile for SaveGame {
+    const VERSION: u32 = 1;
+}
+
+impl SaveGame {
+    /// Saves the state of a running game.
+    /// Buildings are not saved, as they are spawned again from the game data.
+    pub(super) fn new(config: &GameConfig, world: &World, resources: &Resources) -> Option<Self> {
+        let spell_pool = resources.get::<components::spell::SpellPool>()?;
+
+        // collect all saved entities first, so immunities can refer to them by index
+        let saved: Vec<Entity> = <Entity>::query()
+            .iter(world)
+            .copied()
+            .filter(|ent| {
+                world.entry_ref(*ent).is_ok_and(|entry| {
+                    entry
+                        .get_component::<components::buildings::Building>()
+                        .is_err()
+                })
+            })
+            .collect();
+        let indices: HashMap<Entity, usize> = saved
+            .iter()
+            .enumerate()
+            .map(|(index, ent)| (*ent, index))
+            .collect();
+
+        let mut entities = Vec::with_capacity(saved.len());
+        for ent in saved {
+            let entry = world.entry_ref(ent).ok()?;
+            let collision = entry.get_component::<components::Collision>().ok();
+            entities.push(SavedEntity {
+                position: entry.get_component::<components::Position>().ok().copied(),
+                velocity: entry.get_component::<components::Velocity>().ok().copied(),
+                graphics: entry.get_component::<components::Graphics>().ok().cloned(),
+                health: entry.get_component::<components::Health>().ok().cloned(),
+                enemy: entry.get_component::<components::Enemy>().ok().copied(),
+                life_duration: entry
+                    .get_component::<components::LifeDuration>()
+                    .ok()
+                    .cloned(),
+                immunity: collision
+                    .map(|collision| {
+                        collision
+                            .get_immunity()
+                            .iter()
+                            .filter_map(|other| indices.get(other).copied())
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                collision: collision.cloned(),
+                boundary_collision: entry
+                    .get_component::<components::BoundaryCollision>()
+                    .ok()
+                    .copied(),
+                actions: entry.get_component::<components::Actions>().ok().cloned(),
+                control: entry.get_component::<components::Control>().ok().cloned(),
+                spell_origin: entry
+                    .get_component::<components::SpellOrigin>()
+                    .ok()
+                    .copied(),
+                boss: entry.get_component::<components::Boss>().ok().cloned(),
+                spells: entry
+                    .get_component::<components::SpellCaster>()
+                    .ok()
+                    .map(|caster| {
+                        caster
+                            .get_spells()
+                            .iter()
+                            .map(|spell| components::spell::get_spell_index(&spell_pool, spell))
+                            .collect()
+                    }),
+                spell_slots: entry
+                    .get_component::<components::SpellCaster>()
+                    .map(|caster| caster.get_spell_slots().to_vec())
+                    .unwrap_or_default(),
+            });
+        }
+
+        Some(Self {
+            config: config.clone(),
+            seed: resources
+                .get::<RunStats>()
+                .map(|stats| stats.seed)
+                .unwrap_or_default(),
+            rng: Some(SavedRng::new(&*resources.get::<GameRng>()?)),
+            game_data: resources.get::<game_data::GameData>()?.clone(),
+            director: resources.get::<director::Director>()?.clone(),
+            spell_levels: components::spell::get_spell_levels(&spell_pool),
+            stats: resources
+                .get::<RunStats>()
+                .map(|stats| stats.clone())
+                .unwrap_or_default(),
+            entities,
+        })
+    }
+
+    /// Pushes all saved entities into the world.
+    /// Returns the entity controlled by the player, if one was saved.
+    pub(super) fn restore_entities(
+        &self,
+        world: &mut World,
+        spell_pool: &components::spell::SpellPool,
+        sprite_pool: &sprite::SpritePool,
+    ) -> Option<Entity> {
+        let entities: Vec<Entity> = self
+            .entities
+            .iter()
+            .map(|saved| {
+                world.push((saved
+                    .actions
+                    .clone()
+                    .unwrap_or_else(components::Actions::new),))
+            })
+            .collect();
+
+        let mut player = None;
+
+        for (saved, &ent) in self.entities.iter().zip(entities.iter()) {
+            let Some(mut entry) = world.entry(ent) else {
+                continue;
+            };
+            if let Some(position) = saved.position {
+                entry.add_component(position);
+            }
+            if let Some(velocity) = saved.velocity {
+                entry.add_component(velocity);
+            }
+            if let Some(graphics) = &saved.graphics {
+                entry.add_component(graphics.clone());
+            }
+            if let Some(health) = &saved.health {
+                entry.add_component(health.clone());
+            }
+            if let Some(enemy) = saved.enemy {
+                entry.add_component(enemy);
+            }
+            if let Some(life_duration) = &saved.life_duration {
+                entry.add_component(life_duration.clone());
+            }
+            if let Some(collision) = &saved.collision {
+                entry.add_component(
+                    saved
+                        .immunity
+                        .iter()
+                        .filter_map(|&index| entities.get(index))
+                        .fold(collision.clone(), |collision, &other| {
+                            collision.with_immunity(other)
+                        }),
+                );
+            }
+            if let Some(boundary_collision) = saved.boundary_collision {
+                entry.add_component(boundary_collision);
+            }
+            if let Some(spell_origin) = saved.spell_origin {
+                entry.add_component(spell_origin);
+            }
+            if let Some(boss) = &saved.boss {
+                entry.add_component(boss.clone());
+            }
+            if let Some(control) = &saved.control {
+                entry.add_component(control.clone());
+                player = Some(ent);
+            }
+            if let Some(spells) = &saved.spells {
+                entry.add_component(
+                    components::SpellCaster::new(
+                        components::spell::init_base_spells(spell_pool, sprite_pool, spells),
+                        self.config.base_slots,
+                    )
+                    .with_spell_slots(&saved.spell_slots),
+                );
+            }
+        }
+
+        player
+    }
+
+    /// Returns the random number generator of the saved run.
+    /// Saves without its state resume with a generator seeded from the saved seed.
+    pub(super) fn restore_rng(&self) -> GameRng {
+        self.rng
+            .as_ref()
+            .and_then(SavedRng::restore)
+            .unwrap_or_else(|| GameRng::seed_from_u64(self.seed))
+    }
+
+    /// Saves this save game to the given path.
+    pub fn save_to_file(
+        &self,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        crate::storage::save(path, self)
+    }
+
+    /// Returns wether a saved run exists at the default save game path.
+    pub fn exists() -> bool {
+        crate::paths::save_path(SAVEGAME_PATH).exists()
+    }
+}
@@ -0,0 +1,574 @@
+use ggez::graphics;
+use legion::{
+    component, systems::CommandBuffer, Entity, EntityStore, IntoQuery, Resources, Schedule, World,
+};
+use mooeye::{sprite, ui as mui};
+use rand::{Rng, SeedableRng};
+
+use std::{collections::HashMap, time::Duration};
+
+use super::controller::Command;
+use super::savegame::SaveGame;
+use super::{
+    achievements, components, director, game_data, mutators, run_stats::RunStats, Interactions,
+    MessageQueue, BOUNDARIES,
+};
+use super::{GameConfig, GameMessage, GameRng};
+
+/// The fixed duration of a single simulation tick.
+pub const TICK: Duration = Duration::from_nanos(1_000_000_000 / 60);
+/// The maximum amount of time the simulation catches up on in a single step. Protects against freezing after long hitches.
+const MAX_ACCUMULATION: Duration = Duration::from_millis(250);
+
+/// The simulation core of a game.
+/// Contains the ECS world & resources and the schedule that advances them, but nothing that requires a window, audio device or [ggez::Context].
+/// Rendering and audio attach to a simulation only when they are present.
+pub struct Simulation {
+    /// The ECS worlds, containing all acting entities.
+    pub(super) world: World,
+    /// The ECS resources, data that must be available to all systems and is not bound to certain entities.
+    pub(super) resources: Resources,
+    /// The main gameplay schedule, producing and consuming actions
+    action_prod_schedule: Schedule,
+    /// The time passed that has not yet been simulated in a tick.
+    accumulator: Duration,
+    /// Single-event commands that have been issued but not yet been passed to a tick.
+    pending_commands: HashMap<Command, bool>,
+}
+
+impl Simulation {
+    /// Creates a new simulation from a game config.
+    /// The sprite pool is only used to look up spell and enemy icons and may be empty.
+    pub fn new(
+        config: &GameConfig,
+        sprite_pool: sprite::SpritePool,
+        achievements: &achievements::AchievementSet,
+    ) -> Self {
+        // --- WORLD CREATION ---
+
+        let mut world = World::default();
+
+        // --- RESOURCE INITIALIZATION ---
+
+        let seed = config.seed.unwrap_or_else(GameConfig::random_seed);
+        let mut rng = GameRng::seed_from_u64(seed);
+
+        let boundaries = BOUNDARIES;
+        let mut spell_pool = components::spell::init_spell_pool(&sprite_pool, achievements);
+        let mut game_data =
+            game_data::GameData::new(config.starting_gold, config.starting_city_health)
+                .with_score_factor(mutators::score_factor(&config.mutators));
+        for mutator in &config.mutators {
+            mutator.apply_to_spell_pool(&mut spell_pool);
+            mutator.apply_to_game_data(&mut game_data);
+        }
+        components::spell::check_castable(&spell_pool);
+        let director = director::Director::new(&sprite_pool, config);
+
+        Self::initalize_environment(&boundaries, &mut world, &mut rng);
+        Self::push_mutator_effects(&mut world, &config.mutators);
+
+        // Add player
+
+        let player = Self::push_player(&mut world, config, &spell_pool, &sprite_pool);
+
+        Self::from_parts(
+            world,
+            player,
+            game_data,
+            director,
+            spell_pool,
+            sprite_pool,
+            rng,
+            RunStats::new(seed),
+        )
+    }
+
+    /// Resumes a simulation from a save game.
+    /// The sprite pool is only used to look up spell and enemy icons and may be empty.
+    pub fn from_save(
+        save: SaveGame,
+        sprite_pool: sprite::SpritePool,
+        achievements: &achievements::AchievementSet,
+    ) -> Self {
+        let mut world = World::default();
+
+        let rng = save.restore_rng();
+
+        let mut spell_pool = components::spell::init_spell_pool(&sprite_pool, achievements);
+        for mutator in &save.config.mutators {
+            mutator.apply_to_spell_pool(&mut spell_pool);
+        }
+        components::spell::check_castable(&spell_pool);
+        components::spell::restore_spell_levels(&mut spell_pool, &save.spell_levels);
+
+        let player = save
+            .restore_entities(&mut world, &spell_pool, &sprite_pool)
+            .unwrap_or_else(|| {
+                Self::push_player(&mut world, &save.config, &spell_pool, &sprite_pool)
+            });
+
+        components::buildings::restore_buildings(
+            &mut world,
+            &save.game_data.buildings,
+            &BOUNDARIES,
+        );
+
+        let director = save.director.with_enemies(&sprite_pool);
+
+        let simulation = Self::from_parts(
+            world,
+            player,
+            save.game_data,
+            director,
+            spell_pool,
+            sprite_pool,
+            rng,
+            save.stats,
+        );
+
+        // show the health bars of bosses that were alive when the game was saved
+        let bosses: Vec<u8> = <&components::Boss>::query()
+            .iter(&simulation.world)
+            .map(|boss| boss.get_id())
+            .collect();
+        if let Some(mut message_queue) = simulation.resources.get_mut::<MessageQueue>() {
+            for boss in bosses {
+                message_queue.push(mui::UiMessage::Extern(GameMessage::BossSpawned { boss }));
+            }
+        }
+
+        simulation
+    }
+
+    /// Saves the current state of this simulation, so it can be resumed via [Simulation::from_save].
+    pub fn save(&self, config: &GameConfig) -> Option<SaveGame> {
+        SaveGame::new(config, &self.world, &self.resources)
+    }
+
+    /// Pushes a new player entity as described by the config to the world.
+    fn push_player(
+        world: &mut World,
+        config: &GameConfig,
+        spell_pool: &components::spell::SpellPool,
+        sprite_pool: &sprite::SpritePool,
+    ) -> Entity {
+        world.push((
+            components::Position::new(BOUNDARIES.w / 2., BOUNDARIES.h - 64.),
+            components::BoundaryCollision::new(true, false, false),
+            components::Control::new(config.base_speed),
+            components::Graphics::new("/sprites/mage2", Duration::from_secs_f32(0.25)),
+            components::SpellCaster::new(
+                components::spell::init_base_spells(spell_pool, sprite_pool, &config.base_spells),
+                config.base_slots,
+            ),
+        ))
+    }
+
+    /// Pushes an invisible entity to the world that applies the global effects of all passed mutators.
+    /// It is saved like any other entity, so resumed games don't push it again.
+    fn push_mutator_effects(world: &mut World, mutators: &[mutators::Mutator]) {
+        let effects: Vec<_> = mutators
+            .iter()
+            .flat_map(|mutator| mutator.global_effects())
+            .collect();
+        if !effects.is_empty() {
+            world.push((
+                components::Position::new(0., 0.),
+                effects
+                    .into_iter()
+                    .fold(components::Actions::new(), |actions, effect| {
+                        actions.with_effect(effect)
+                    }),
+            ));
+        }
+    }
+
+    /// Creates a simulation from a world and all resources that differ between new and resumed games.
+    #[allow(clippy::too_many_arguments)]
+    fn from_parts(
+        world: World,
+        player: Entity,
+        game_data: game_data::GameData,
+        director: director::Director,
+        spell_pool: components::spell::SpellPool,
+        sprite_pool: sprite::SpritePool,
+        rng: GameRng,
+        stats: RunStats,
+    ) -> Self {
+        // insert this to make sure the city health is displayed correctly
+        let message_queue: MessageQueue = vec![mui::UiMessage::Extern(
+            GameMessage::UpdateCityHealth(game_data.city_health),
+        )];
+
+        // --- RESOURCE INSERTION ---
+
+        let mut resources = Resources::default();
+        resources.insert(player);
+        resources.insert(game_data);
+        resources.insert(message_queue);
+        resources.insert(BOUNDARIES);
+        resources.insert(director);
+        resources.insert(spell_pool);
+        resources.insert(sprite_pool);
+        resources.insert(rng);
+        resources.insert(stats);
+
+        Self {
+            world,
+            resources,
+            action_prod_schedule: Self::build_schedule(false),
+            accumulator: Duration::ZERO,
+            pending_commands: HashMap::new(),
+        }
+    }
+
+    #[cfg_attr(not(test), allow(dead_code))]
+    /// Creates a new simulation without any assets, for running gameplay without a window.
+    pub fn new_headless(config: &GameConfig) -> Self {
+        let achievements =
+            achievements::AchievementSet::load(None, config.achievements_unlocked.clone());
+        Self::new(config, sprite::SpritePool::new(), &achievements)
+    }
+
+    /// Attaches an audio pool to this simulation, making it enqueue the sounds played by entities, and returns it builder-pattern style.
+    pub fn with_audio(mut self, audio_pool: components::audio::AudioPool) -> Self {
+        self.resources.insert(audio_pool);
+        self.action_prod_schedule = Self::build_schedule(true);
+        self
+    }
+
+    /// Builds the main gameplay schedule. Sound enqueueing is only added if an audio pool is attached.
+    fn build_schedule(audio: bool) -> Schedule {
+        let mut builder = Schedule::builder();
+        builder
+            // remember positions for interpolation
+            .add_system(components::position::remember_positions_system())
+            .flush()
+            // director
+            .add_system(director::direct_system())
+            // sytems that produce actions
+            .add_system(components::collision::collision_system())
+            .add_system(components::position::velocity_system())
+            .add_system(components::health::enemy_system())
+            .add_system(components::control::control_system())
+            .add_system(components::duration::manage_durations_system())
+            .add_system(components::health::destroy_by_health_system())
+            .flush()
+            // systems that consume (but may produce) actions
+            .add_system(components::spell::spell_casting_system())
+            .add_system(components::boss::boss_phases_system())
+            .add_system(components::actions::handle_effects_system())
+            .flush()
+            // buildings
+            .add_system(components::buildings::destroy_buildings_system())
+            .add_system(components::buildings::create_buildings_system())
+            // systems that consume actions
+            .add_system(components::actions::resolve_executive_actions_system())
+            .add_system(components::graphics::handle_particles_system());
+        if audio {
+            builder.add_system(components::audio::audio_enqueue_system());
+        }
+        builder
+            .add_system(components::position::resolve_move_system())
+            .add_system(components::collision::boundary_collision_system())
+            .add_system(components::collision::resolve_immunities_system())
+            .add_system(components::health::resolve_damage_system())
+            .add_system(components::actions::apply_silence_system())
+            .add_system(game_data::resolve_gama_data_system())
+            .add_system(components::health::enemy_death_sprite_system())
+            .add_system(components::health::remove_entities_system())
+            .add_system(components::actions::clear_system())
+            .build()
+    }
+
+    /// Initializes the environment by spawning house and brush sprites.
+    fn initalize_environment(boundaries: &graphics::Rect, world: &mut World, rng: &mut GameRng) {
+        // Create cobble sprites
+        for _i in 0..48 {
+            world.push((
+                components::Position::new(
+                    boundaries.w * rng.gen::<f32>(),
+                    boundaries.h * (rng.gen::<f32>() * 2. - 0.5),
+                ),
+                components::Graphics::new("/sprites/environment/cobble", Duration::ZERO)
+                    .with_sprite_variant(rng.gen::<u32>()),
+            ));
+        }
+
+        let building_size = 4. * 32.;
+
+        // Add tree sprites
+        let mut positions = Vec::new();
+        for _i in 0..12 {
+            let rand_x = rng.gen::<f32>() * 8. - 4.;
+            positions.push(components::Position::new(
+                (rand_x) * building_size + if rand_x > 0. { boundaries.w } else { 0. },
+                (rng.gen::<f32>() * 0.7 - 0.2) * boundaries.h,
+            ));
+        }
+        positions.sort_by(|p1, p2| {
+            p1.y.partial_cmp(&p2.y)
+                .expect("[ERROR/Radish] Ordering of y-coordinates in brush init failed.")
+        });
+        for pos in positions {
+            world.push((
+                pos,
+                components::Graphics::new("/sprites/environment/tree", Duration::ZERO)
+                    .with_sprite_variant(rng.gen::<u32>()),
+            ));
+        }
+
+        // Add building sprites
+        for (x, y) in [
+            // left side
+            (-1.2, -1.8),
+            (-0.55, -0.6),
+            (-2.6, -0.65),
+            (-2.1, 0.5),
+            (-0.9, 0.67),
+            (-1.6, -0.55),
+            // right side
+            (0.8, -1.6),
+            (2.1, -1.2),
+            (0.7, -0.3),
+            (2.4, -0.5),
+            (0.8, 0.8),
+            (2.3, 0.6),
+        ] {
+            world.push((
+                components::Position::new(
+                    building_size * x + if x > 0. { boundaries.w } else { 0. },
+                    y * building_size + boundaries.h,
+                ),
+                components::Graphics::new("/sprites/environment/building", Duration::ZERO)
+                    .with_sprite_variant(rng.gen::<u32>()),
+            ));
+        }
+    }
+
+    /// A helper function that ensures every entity in the world has a certain component subset
+    fn ensure_default_components(&mut self) {
+        // running buffer of added components
+        let mut buffer = CommandBuffer::new(&self.world);
+
+        // query for all elements not equipped with an ACTIONS module
+        for ent in <Entity>::query()
+            .filter(!component::<components::Actions>())
+            .iter(&self.world)
+        {
+            buffer.add_component(*ent, components::Actions::new());
+        }
+
+        // query for all positioned elements not remembering their previous position
+        for (ent, pos) in <(Entity, &components::Position)>::query()
+            .filter(!component::<components::PreviousPosition>())
+            .iter(&self.world)
+        {
+            buffer.add_component(*ent, components::PreviousPosition(*pos));
+        }
+
+        buffer.flush(&mut self.world, &mut self.resources);
+    }
+
+    /// Advances the simulation by the time passed in the interactions, using them as player input.
+    /// The schedule is executed in fixed ticks, so the passed time may result in any number of ticks.
+    /// Single-event commands (like spell casts) are passed to exactly one tick, continuous ones (like movement) to every tick of this step.
+    pub fn step(&mut self, interactions: Interactions) {
+        self.accumulator = (self.accumulator + interactions.delta).min(MAX_ACCUMULATION);
+
+        for (&command, &active) in interactions.commands.iter() {
+            if active && !command.is_continuous() {
+                self.pending_commands.insert(command, true);
+            }
+        }
+
+        while self.accumulator >= TICK {
+            self.accumulator -= TICK;
+
+            let mut commands = std::mem::take(&mut self.pending_commands);
+            for (&command, &active) in interactions.commands.iter() {
+                if command.is_continuous() {
+                    commands.insert(command, active);
+                }
+            }
+
+            self.tick(Interactions {
+                commands,
+                delta: TICK,
+            });
+        }
+    }
+
+    /// Executes the gameplay schedule exactly once.
+    fn tick(&mut self, interactions: Interactions) {
+        // insert interactions as resource
+        self.resources.insert(interactions);
+
+        // make sure all entities have all default components
+        self.ensure_default_components();
+
+        self.action_prod_schedule
+            .execute(&mut self.world, &mut self.resources);
+
+        if let Some(mut stats) = self.resources.get_mut::<RunStats>() {
+            stats.duration += TICK.as_secs_f32();
+        }
+    }
+
+    /// Returns how far the simulation has progressed towards the next tick, between 0 and 1.
+    /// Used to interpolate between the previous and current positions when drawing.
+    pub fn get_interpolation(&self) -> f32 {
+        self.accumulator.as_secs_f32() / TICK.as_secs_f32()
+    }
+
+    #[cfg_attr(not(test), allow(dead_code))]
+    /// Steps the simulation once for every passed interaction until the city falls, the campaign is won or the interactions run out.
+    /// As there is no wave menu without a GUI, the next wave is started as soon as the director waits for it.
+    /// Returns the amount of steps taken.
+    pub fn run(&mut self, interactions: impl IntoIterator<Item = Interactions>) -> usize {
+        let mut steps = 0;
+        for ix in interactions {
+            if self.is_game_over() || self.is_victorious() {
+                break;
+            }
+            if let Some(mut director) = self.resources.get_mut::<director::Director>() {
+                director.next_wave();
+            }
+            self.step(ix);
+            self.drain_messages();
+            steps += 1;
+        }
+        steps
+    }
+
+    /// Removes and returns all messages the systems produced since the last call.
+    /// They are also counted in the statistics of the run.
+    pub fn drain_messages(&mut self) -> MessageQueue {
+        let messages = self
+            .resources
+            .get_mut::<MessageQueue>()
+            .map(|mut message_queue| std::mem::take(&mut *message_queue))
+            .unwrap_or_default();
+        if let Some(mut stats) = self.resources.get_mut::<RunStats>() {
+            stats.receive(&messages);
+        }
+        messages
+    }
+
+    /// Returns the statistics of this run so far.
+    pub fn get_stats(&self) -> RunStats {
+        self.resources
+            .get::<RunStats>()
+            .map(|stats| stats.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the names of the spells the player currently has equipped.
+    pub fn get_equipped_spells(&self) -> Vec<String> {
+        self.resources
+            .get::<Entity>()
+            .and_then(|player| {
+                self.world
+                    .entry_ref(*player)
+                    .ok()?
+                    .get_component::<components::SpellCaster>()
+                    .ok()
+                    .map(|caster| {
+                        caster
+                            .get_spells()
+                            .iter()
+                            .map(|spell| spell.get_name().to_owned())
+                            .collect()
+                    })
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the current wave.
+    pub fn get_wave(&self) -> u32 {
+        self.resources
+            .get::<director::Director>()
+            .map(|director| director.get_wave())
+            .unwrap_or_default()
+    }
+
+    /// Returns wether the director is currently waiting for the next wave to be started.
+    pub fn is_between_waves(&self) -> bool {
+        self.resources
+            .get::<director::Director>()
+            .map(|director| director.is_between_waves())
+            .unwrap_or(false)
+    }
+
+    /// Returns wether the last wave of the campaign has been beaten.
+    pub fn is_victorious(&self) -> bool {
+        self.resources
+            .get::<director::Director>()
+            .map(|director| director.is_victorious())
+            .unwrap_or(false)
+    }
+
+    /// Returns wether the city has fallen.
+    pub fn is_game_over(&self) -> bool {
+        self.resources
+            .get::<game_data::GameData>()
+            .map(|game_data| game_data.city_health <= 0)
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a config with a fixed seed that does not depend on the progress saved on this machine.
+    fn test_config() -> GameConfig {
+        GameConfig {
+            seed: Some(1234),
+            achievements_unlocked: achievements::AchievementProgressSource::Percentage(1.),
+            ..Default::default()
+        }
+    }
+
+    /// Returns two minutes of scripted input: walking back and forth and casting every spell in turn.
+    fn test_inputs() -> Vec<Interactions> {
+        (0..120 * 60)
+            .map(|tick| {
+                let mut commands = HashMap::new();
+                if tick % 240 < 120 {
+                    commands.insert(Command::MoveLeft, true);
+                } else {
+                    commands.insert(Command::MoveRight, true);
+                }
+                if tick % 20 == 0 {
+                    commands.insert(Command::spell_from_int(tick / 20 % 4), true);
+                }
+                Interactions {
+                    commands,
+                    delta: TICK,
+                }
+            })
+            .collect()
+    }
+
+    /// Runs a headless simulation on the test inputs and returns its saved end state.
+    fn run_headless() -> String {
+        let config = test_config();
+        let mut simulation = Simulation::new_headless(&config);
+        let steps = simulation.run(test_inputs());
+        assert!(steps > 0);
+        assert!(simulation.get_stats().kills > 0);
+        toml::to_string(
+            &simulation
+                .save(&config)
+                .expect("the simulation could not be saved"),
+        )
+        .expect("the save game could not be serialized")
+    }
+
+    #[test]
+    fn headless_runs_are_deterministic() {
+        assert_eq!(run_headless(), run_headless());
+    }
+}
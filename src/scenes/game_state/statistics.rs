@@ -0,0 +1,99 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::run_stats::{by_name, RunStats, StatNames};
+
+/// The path of the file lifetime statistics are saved to, relative to the save folder.
+pub const STATISTICS_PATH: &str = "statistics.toml";
+
+/// Totals accumulated over all runs ever played, apart from replays.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LifetimeStats {
+    /// The amount of runs finished or abandoned.
+    #[serde(default)]
+    pub runs: u32,
+    /// The time spent in runs, in seconds.
+    #[serde(default)]
+    pub playtime: f64,
+    /// The amount of waves cleared.
+    #[serde(default)]
+    pub waves: u64,
+    /// The gold earned, before score factors are applied.
+    #[serde(default)]
+    pub gold_earned: u64,
+    /// The gold spent in the wave menu.
+    #[serde(default)]
+    pub gold_spent: u64,
+    /// The amount of enemies killed, by enemy name.
+    #[serde(default)]
+    pub kills: BTreeMap<String, u64>,
+    /// The amount of casts, by spell name.
+    #[serde(default)]
+    pub casts: BTreeMap<String, u64>,
+    /// The damage dealt to enemies, by spell name.
+    #[serde(default)]
+    pub damage: BTreeMap<String, u64>,
+    /// The amount of times each building was constructed, by building name.
+    #[serde(default)]
+    pub buildings_built: BTreeMap<String, u64>,
+    /// The amount of times each building was destroyed, by building name.
+    #[serde(default)]
+    pub buildings_lost: BTreeMap<String, u64>,
+}
+
+impl crate::storage::SaveFile for LifetimeStats {
+    const VERSION: u32 = 1;
+}
+
+impl LifetimeStats {
+    /// Adds the statistics of a finished or abandoned run to the totals.
+    /// Identifiers of enemies, spells and buildings are resolved to their names, so the totals outlive changes to the data files.
+    pub fn record_run(&mut self, run: &RunStats) {
+        self.runs += 1;
+        self.playtime += run.duration as f64;
+        self.waves += run.waves as u64;
+        self.gold_earned += run.gold_earned as u64;
+        self.gold_spent += run.gold_spent as u64;
+
+        let names = StatNames::load();
+        add(
+            &mut self.kills,
+            by_name(&run.enemy_kills, |id| names.enemy(id)),
+        );
+        add(
+            &mut self.casts,
+            by_name(&run.spell_casts, |id| names.spell(id)),
+        );
+        add(
+            &mut self.damage,
+            by_name(&run.spell_damage, |id| names.spell(id)),
+        );
+        add(
+            &mut self.buildings_built,
+            by_name(&run.buildings_built, |index| names.building(index)),
+        );
+        add(
+            &mut self.buildings_lost,
+            by_name(&run.buildings_lost, |index| names.building(index)),
+        );
+    }
+
+    /// Returns the total amount of enemies killed.
+    pub fn total_kills(&self) -> u64 {
+        self.kills.values().sum()
+    }
+
+    /// Returns the total playtime formatted as hours and minutes.
+    pub fn format_playtime(&self) -> String {
+        let minutes = (self.playtime / 60.) as u64;
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    }
+}
+
+/// Adds the passed named counters to the totals of the passed map.
+fn add(totals: &mut BTreeMap<String, u64>, counters: BTreeMap<String, u64>) {
+    for (name, count) in counters {
+        *totals.entry(name).or_default() += count;
+    }
+}
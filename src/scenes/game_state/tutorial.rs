@@ -94,7 +94,7 @@ impl TutorialManager {
                     "Defend the town!",
                     "Kill skeletons before they reach the town!\n\n\
                     Move with A/D and cast spells with J/K/L/;. You can hover your spells to see what they do.",
-                    game_message::UiMessageFilter::Ext(game_message::GameMessage::UpdateCityHealth(0), game_message::GameMessageFilter::Type),
+                    game_message::UiMessageFilter::Ext(game_message::GameMessagePredicate::CityHealth),
                 ),
                 TutorialMessage::new(
                     "You just cast a spell",
@@ -102,15 +102,19 @@ impl TutorialManager {
                     More powerful spells block more spell slots, and for longer.\n\n\
                     You can view the status of your spell slots in the top left.\n\n\
                     Hover over your spells to see their spell slot requirements.",
-                    game_message::UiMessageFilter::Ext(game_message::GameMessage::UpdateSpellSlots(0, 16), game_message::GameMessageFilter::Max),
+                    game_message::UiMessageFilter::Ext(game_message::GameMessagePredicate::SpellCast { spell: None }),
                 ),
                 TutorialMessage::new(
                     "You killed an enemy",
                     "Killing enemies grants you gold and increases your total score.\n\n\
                     Gold is used to purchase upgrades between waves.",
                     game_message::UiMessageFilter::Ext(
-                        game_message::GameMessage::EnemyKilled(0),
-                        game_message::GameMessageFilter::Type,
+                        game_message::GameMessagePredicate::EnemyKilled {
+                            enemy: None,
+                            elite: None,
+                            spell: None,
+                            min_bounty: None,
+                        },
                     ),
                 ),
                 TutorialMessage::new(
@@ -118,8 +122,12 @@ impl TutorialManager {
                     "Elite enemies are tougher than normal enemies, have more abilities and deal more damage to your city.\n\n\
                     But they are also worth a lot more gold!",
                     game_message::UiMessageFilter::Ext(
-                        game_message::GameMessage::EnemyKilled(10),
-                        game_message::GameMessageFilter::Min,
+                        game_message::GameMessagePredicate::EnemyKilled {
+                            enemy: None,
+                            elite: Some(true),
+                            spell: None,
+                            min_bounty: None,
+                        },
                     ),
                 ),
                 TutorialMessage::new(
@@ -127,7 +135,7 @@ impl TutorialManager {
                     "You survived the first wave! Take some time to look around town.\n\n\
                     Click the three left icons to look at your available options.\n\n\
                     When you are done, use the right arrow icon to start the next wave.",
-                    game_message::UiMessageFilter::Ext(game_message::GameMessage::NextWave(2),game_message::GameMessageFilter::Equality),
+                    game_message::UiMessageFilter::Ext(game_message::GameMessagePredicate::NextWave { wave: Some(2) }),
                 ),
                 TutorialMessage::new(
                     "Lookout",
@@ -164,8 +172,10 @@ impl TutorialManager {
                     "Constructing a building gives you a permanent upgrade.\n\n\
                     Additionally, buildings can protect your town. Enemies getting past you will first destroy your buildings before damaging your town.",
                     game_message::UiMessageFilter::Ext(
-                        game_message::GameMessage::BuildingUp(0, 0),
-                        game_message::GameMessageFilter::Type,
+                        game_message::GameMessagePredicate::BuildingUp {
+                            building: None,
+                            min_level: None,
+                        },
                     ),
                 ),
                 TutorialMessage::new(
@@ -173,8 +183,7 @@ impl TutorialManager {
                     "Enemies getting past you will first destroy your buildings before damaging your town.\n\n\
                     You can always rebuild the destroyed building.",
                     game_message::UiMessageFilter::Ext(
-                        game_message::GameMessage::BuildingDown(0, 0),
-                        game_message::GameMessageFilter::Type,
+                        game_message::GameMessagePredicate::BuildingLost { building: None },
                     ),
                 ),
             ],
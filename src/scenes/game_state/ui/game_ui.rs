@@ -13,6 +13,8 @@ pub const ID_SPELL_BAR: u32 = 60;
 pub const ID_SPELL_BAR_CHILDREN: u32 = 61;
 pub const ID_MANA_BAR: u32 = 50;
 pub const ID_MANA_SLOT: u32 = 51;
+pub const ID_BOSS_BOX: u32 = 70;
+pub const ID_BOSS_BAR: u32 = 71;
 
 /// Constructs the UiElement that forms the main UI of the game.
 /// Consists of
@@ -21,6 +23,7 @@ pub const ID_MANA_SLOT: u32 = 51;
 ///  - Cooldown display for the players spell slots
 ///  - Indicator for currently equipped spells
 ///  - Vertical box to display messages (achievements etc.)
+///  - Container for the health bar of the current boss
 pub fn construct_game_ui(
     ctx: &ggez::Context,
     config: super::game_state::GameConfig,
@@ -190,6 +193,16 @@ pub fn construct_game_ui(
         )
         .build();
 
+    let boss_box = ui::containers::VerticalBox::new()
+        .to_element_builder(ID_BOSS_BOX, ctx)
+        .with_alignment(ui::Alignment::Center, ui::Alignment::Min)
+        .with_offset(0., 16.)
+        .with_size(
+            ui::Size::Shrink(0., f32::INFINITY),
+            ui::Size::Shrink(0., f32::INFINITY),
+        )
+        .build();
+
     let tutorial_box = ui::containers::VerticalBox::new()
         .to_element_builder(super::super::tutorial::TUTORIAL_BOX, ctx)
         .with_alignment(ui::Alignment::Max, ui::Alignment::Center)
@@ -203,6 +216,7 @@ pub fn construct_game_ui(
     Ok(ui::containers::StackBox::new()
         .to_element_builder(0, ctx)
         .with_child(achievement_box)
+        .with_child(boss_box)
         .with_child(tutorial_box)
         .with_child(data_box)
         .with_child(slot_box)
@@ -212,6 +226,79 @@ pub fn construct_game_ui(
         .build())
 }
 
+/// Adds and removes the health bars of bosses as they enter and leave the battlefield.
+pub fn handle_boss_bar(
+    messages: &[ui::UiMessage<game_state::GameMessage>],
+    gui: &mut ui::UiElement<game_state::GameMessage>,
+    ctx: &ggez::Context,
+    resources: &legion::Resources,
+) {
+    for message in messages {
+        match message {
+            ui::UiMessage::Extern(game_state::GameMessage::BossSpawned { boss }) => {
+                if let Some(name) =
+                    resources
+                        .get::<game_state::director::Director>()
+                        .and_then(|director| {
+                            director.get_boss_by_id(*boss).map(|boss| boss.name.clone())
+                        })
+                {
+                    gui.remove_elements(ID_BOSS_BAR);
+                    gui.add_element(ID_BOSS_BOX, construct_boss_bar(ctx, &name));
+                }
+            }
+            ui::UiMessage::Extern(game_state::GameMessage::BossRemoved { .. }) => {
+                gui.remove_elements(ID_BOSS_BAR);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Constructs a screen-wide health bar displaying the name and health of a boss.
+fn construct_boss_bar(ctx: &ggez::Context, name: &str) -> ui::UiElement<game_state::GameMessage> {
+    let name = graphics::Text::new(
+        graphics::TextFragment::new(name).color(graphics::Color::from_rgb_u32(PALETTE[7])),
+    )
+    .set_scale(32.)
+    .set_font("Retro")
+    .to_owned()
+    .to_element_builder(0, ctx)
+    .with_alignment(ui::Alignment::Center, None)
+    .build();
+
+    let color = graphics::Color::from_rgb_u32(PALETTE[6]);
+    let bar = HealthBar::new(color, 1.)
+        .to_element_builder(0, ctx)
+        .with_message_handler(move |message_set, _layout, transitions| {
+            for message in message_set {
+                if let ui::UiMessage::Extern(game_state::GameMessage::BossHealth {
+                    health,
+                    max_health,
+                    ..
+                }) = message
+                {
+                    transitions.push_back(ui::Transition::new(Duration::ZERO).with_new_content(
+                        HealthBar::new(color, *health as f32 / (*max_health).max(1) as f32),
+                    ));
+                }
+            }
+        })
+        .with_visuals(ui::Visuals {
+            background: graphics::Color::from_rgb_u32(PALETTE[13]),
+            ..super::BUTTON_VIS
+        })
+        .with_size(ui::Size::Fixed(800.), ui::Size::Fixed(24.))
+        .build();
+
+    ui::containers::VerticalBox::new_spaced(4.)
+        .to_element_builder(ID_BOSS_BAR, ctx)
+        .with_child(name)
+        .with_child(bar)
+        .with_visuals(super::BUTTON_VIS)
+        .build()
+}
+
 pub fn create_spellslot(ctx: &ggez::Context, i: usize) -> ui::UiElement<game_state::GameMessage> {
     let mana = graphics::Image::from_path(ctx, "/sprites/spells/mana.png")
         .expect("[ERROR/Radish] Could not unpack mana symbol. Aborting.")
@@ -286,3 +373,38 @@ impl<T: Copy + Eq + Hash> ui::UiContent<T> for Covering {
         );
     }
 }
+
+/// A ui-element that fills another element from the left by a certain amount, like a health bar.
+/// Change the filled amount by using messages and content-changing transitions.
+pub struct HealthBar {
+    /// The filled percentage, between 0 and 1.
+    fill: f32,
+    /// The color of the filled part.
+    color: graphics::Color,
+}
+
+impl HealthBar {
+    /// Creates a new health bar.
+    pub fn new(color: graphics::Color, fill: f32) -> Self {
+        Self {
+            fill: fill.clamp(0., 1.),
+            color,
+        }
+    }
+}
+
+impl<T: Copy + Eq + Hash> ui::UiContent<T> for HealthBar {
+    fn draw_content(
+        &mut self,
+        _ctx: &mut ggez::Context,
+        canvas: &mut graphics::Canvas,
+        param: ui::UiDrawParam,
+    ) {
+        let mut target_mod = param.target;
+        target_mod.w *= self.fill;
+        canvas.draw(
+            &graphics::Quad,
+            param.param.dest_rect(target_mod).color(self.color),
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::{cell::Cell, rc::Rc};
+
 use ggez::{graphics, GameError};
 use mooeye::{scene_manager, ui, ui::UiContent};
 
@@ -5,10 +7,15 @@ use crate::PALETTE;
 
 pub struct InGameMenu {
     gui: ui::UiElement<()>,
+    /// The flag to set to make the paused game save itself and return to the main menu. None if the game can't be saved.
+    save_request: Option<Rc<Cell<bool>>>,
 }
 
 impl InGameMenu {
-    pub fn new(ctx: &ggez::Context) -> Result<Self, GameError> {
+    pub fn new(
+        ctx: &ggez::Context,
+        save_request: Option<Rc<Cell<bool>>>,
+    ) -> Result<Self, GameError> {
         // title
         let pause = graphics::Text::new(
             graphics::TextFragment::new("PAUSED").color(graphics::Color::from_rgb_u32(PALETTE[6])),
@@ -60,6 +67,20 @@ impl InGameMenu {
         .with_trigger_sound(ggez::audio::Source::new(ctx, "/audio/sounds/ui/blipSelect.wav").ok())
         .build();
 
+        let save_quit = graphics::Text::new(
+            graphics::TextFragment::new("Save & Quit")
+                .color(graphics::Color::from_rgb_u32(PALETTE[6])),
+        )
+        .set_font("Retro")
+        .set_scale(32.)
+        .to_owned()
+        .to_element_builder(5, ctx)
+        .with_trigger_key(ggez::winit::event::VirtualKeyCode::S)
+        .with_visuals(super::BUTTON_VIS)
+        .with_hover_visuals(super::BUTTON_HOVER_VIS)
+        .with_trigger_sound(ggez::audio::Source::new(ctx, "/audio/sounds/ui/blipSelect.wav").ok())
+        .build();
+
         let main_menu = graphics::Text::new(
             graphics::TextFragment::new("Return to Main Menu")
                 .color(graphics::Color::from_rgb_u32(PALETTE[6])),
@@ -81,14 +102,23 @@ impl InGameMenu {
             .with_child(pause)
             .with_child(resume)
             .with_child(achievements)
-            .with_child(options)
-            .with_child(main_menu)
-            .with_visuals(super::BUTTON_VIS)
-            .with_alignment(ui::Alignment::Center, ui::Alignment::Center)
-            .with_padding((25., 25., 25., 25.))
-            .build();
-
-        Ok(Self { gui: menu_box })
+            .with_child(options);
+
+        let menu_box = if save_request.is_some() {
+            menu_box.with_child(save_quit)
+        } else {
+            menu_box
+        }
+        .with_child(main_menu)
+        .with_visuals(super::BUTTON_VIS)
+        .with_alignment(ui::Alignment::Center, ui::Alignment::Center)
+        .with_padding((25., 25., 25., 25.))
+        .build();
+
+        Ok(Self {
+            gui: menu_box,
+            save_request,
+        })
     }
 }
 
@@ -117,6 +147,14 @@ impl scene_manager::Scene for InGameMenu {
             );
         }
 
+        if messages.contains(&ui::UiMessage::Triggered(5)) {
+            // the game saves itself on its next update
+            if let Some(save_request) = &self.save_request {
+                save_request.set(true);
+                res = scene_manager::SceneSwitch::pop(1);
+            }
+        }
+
         if messages.contains(&ui::UiMessage::Triggered(4)) {
             res = scene_manager::SceneSwitch::replace(
                 crate::scenes::main_menu::MainMenu::new(ctx)?,
@@ -25,7 +25,7 @@ const ID_SPELL_EQUIP_START: u32 = 230;
 const ID_SPELL_AVAIL_START: u32 = 240;
 
 pub fn handle_wave_menu(
-    messages: &game_state::MessageSet,
+    messages: &[ui::UiMessage<game_state::GameMessage>],
     gui: &mut ui::UiElement<game_state::GameMessage>,
     ctx: &ggez::Context,
     world: &mut legion::World,
@@ -35,7 +35,7 @@ pub fn handle_wave_menu(
     let mut player_sync_needed = false;
     for message in messages {
         if let ui::UiMessage::Extern(game_state::GameMessage::NextWave(wave)) = message {
-            gui.add_element(0, construct_wave_menu(ctx, (wave - 1) as u32));
+            open_wave_menu(ctx, gui, (wave - 1) as u32);
             player_sync_needed = true;
             break;
         }
@@ -48,6 +48,7 @@ pub fn handle_wave_menu(
             if let Ok(mut player) = world.entry_mut(*player_ent);
             if let Ok(caster) = player.get_component_mut::<game_state::components::SpellCaster>();
             if let Some(mut spell_pool) = resources.get_mut::<game_state::components::spell::SpellPool>();
+            if let Some(mut rng) = resources.get_mut::<game_state::GameRng>();
         then{
 
         // enemies submenu
@@ -94,7 +95,7 @@ pub fn handle_wave_menu(
                     if let Some(template) = spell_pool.1.get_mut(index) {
                         // if spell was not yet purchased, attempt to purchase it
                         if template.level == 0
-                            && data.buildings.target[buildings::BuildingType::Mageguild as usize]
+                            && data.buildings.bonuses().unlock_level(buildings::GUILD_UNLOCK)
                                 >= template.guild_condition
                             && data.spend(template.cost)
                         {
@@ -146,9 +147,9 @@ pub fn handle_wave_menu(
         // reroll
         if messages.contains(&ui::UiMessage::Triggered(ID_REROLL))
             && data.spend(director.get_reroll_cost())
-            && data.buildings.target[buildings::BuildingType::Watchtower as usize] > 0
+            && data.buildings.bonuses().reroll
         {
-            director.reroll_wave_enemies();
+            director.reroll_wave_enemies(&mut rng);
             gui.remove_elements(ID_WAVE_SUBMENU);
             gui.add_element(
                 ID_WAVE_SUBMENU_CONT,
@@ -157,21 +158,17 @@ pub fn handle_wave_menu(
         }
 
         // buildings
-        for i in 0..buildings::BUILDING_TYPES {
-            let index = data.buildings.target[i] as usize;
+        for (i, info) in buildings::get_building_list().iter().enumerate() {
+            let level = data.buildings.target.get(i).copied().unwrap_or_default();
             if messages.contains(&ui::UiMessage::Triggered(ID_BUILDINGS_START + i as u32))
-                && index < buildings::BUILDING_MAX_LEVEL
-                && data.spend(
-                    super::game_state::components::buildings::get_building_info(i).level_costs[index]
-                        as i32,
-                )
+                && !data.buildings.is_locked()
+                && info
+                    .upgrade_cost(level)
+                    .is_some_and(|cost| data.spend(cost as i32))
             {
                 data.buildings.target[i] += 1;
-                // if it is the mana well, sync spell slots
-                // if it is the watchtower, sync speed
-                if i == 2 && caster.can_add() || i == 0{
-                    player_sync_needed = true;
-                }
+                // sync the effects of the new level, such as spell slots and speed
+                player_sync_needed = true;
                 // rebuild menu
                 gui.remove_elements(ID_WAVE_SUBMENU);
                 gui.add_element(
@@ -187,7 +184,15 @@ pub fn handle_wave_menu(
             // initialize next wave from director
             director.next_wave();
             // create wave announcer
-            gui.add_element(0, construct_wave_announcer(ctx, director.get_wave()));
+            gui.add_element(
+                0,
+                construct_wave_announcer(
+                    ctx,
+                    director.get_wave(),
+                    director.get_campaign().map(|campaign| campaign.wave.len()),
+                    director.get_difficulty(),
+                ),
+            );
         }
     }
     }
@@ -197,6 +202,16 @@ pub fn handle_wave_menu(
     }
 }
 
+/// Opens the wave menu shown after the passed wave has been survived.
+/// Also used to reopen the menu when resuming a game saved in it, as resumed games don't announce their wave again.
+pub fn open_wave_menu(
+    ctx: &ggez::Context,
+    gui: &mut ui::UiElement<game_state::GameMessage>,
+    wave_survived: u32,
+) {
+    gui.add_element(0, construct_wave_menu(ctx, wave_survived));
+}
+
 /// Construct the last row all three submenus share
 fn construct_wave_menu(
     ctx: &ggez::Context,
@@ -476,6 +491,41 @@ fn construct_enemies_menu(
         );
     }
 
+    // announce the boss of the next wave
+    if let Some(boss) = director.get_boss(director.get_wave() + 1) {
+        enemy_box.add(
+            boss.icon
+                .clone()
+                .to_element_builder(0, ctx)
+                .scaled(4., 4.)
+                .with_visuals(ui::Visuals {
+                    background: graphics::Color::from_rgb_u32(PALETTE[12]),
+                    ..super::BUTTON_VIS
+                })
+                .with_tooltip(
+                    graphics::Text::new(
+                        graphics::TextFragment::new(format!("Boss: {}", boss.name))
+                            .color(graphics::Color::from_rgb_u32(PALETTE[7]))
+                            .scale(36.),
+                    )
+                    .add(graphics::TextFragment::new("\n"))
+                    .add(
+                        graphics::TextFragment::new(&boss.description)
+                            .color(graphics::Color::from_rgb_u32(PALETTE[6]))
+                            .scale(24.),
+                    )
+                    .set_bounds(ggez::glam::Vec2::new(300., 200.))
+                    .set_wrap(true)
+                    .set_font("Retro")
+                    .to_owned()
+                    .to_element_builder(0, ctx)
+                    .with_visuals(super::BUTTON_VIS)
+                    .build(),
+                )
+                .build(),
+        );
+    }
+
     let enemy_box = enemy_box
         .to_element_builder(0, ctx)
         .with_visuals(ui::Visuals {
@@ -484,7 +534,8 @@ fn construct_enemies_menu(
         })
         .build();
 
-    let reroll = if buildings.target[buildings::BuildingType::Watchtower as usize] == 0 {
+    // the enemies of campaign waves are fixed
+    let reroll = if !buildings.bonuses().reroll || director.get_campaign().is_some() {
         ().to_element(0, ctx)
     } else {
         graphics::Image::from_path(ctx, "/sprites/ui/reroll.png")
@@ -498,14 +549,10 @@ fn construct_enemies_menu(
             .with_hover_visuals(super::BUTTON_HOVER_VIS)
             .with_tooltip(
                 graphics::Text::new(
-                    graphics::TextFragment::new(if buildings.target[0] == 0 {
-                        "Purchase the watchtower to reroll enemy waves.".to_owned()
-                    } else {
-                        format!(
-                            "Reroll the enemy selection.\n[M]\nCost: {}g",
-                            director.get_reroll_cost()
-                        )
-                    })
+                    graphics::TextFragment::new(format!(
+                        "Reroll the enemy selection.\n[M]\nCost: {}g",
+                        director.get_reroll_cost()
+                    ))
                     .color(graphics::Color::from_rgb_u32(PALETTE[6])),
                 )
                 .set_scale(24.)
@@ -569,7 +616,8 @@ fn construct_spell_menu(
         .iter()
         .enumerate()
         .fold(
-            ui::containers::GridBox::new_spaced(6, 4, 8., 8.),
+            // six spells per row, as many rows as there are spells in the spell folder
+            ui::containers::GridBox::new_spaced(6, spell_pool.1.len().div_ceil(6).max(1), 8., 8.),
             |mut gbox, (ind, template)| {
                 gbox.add(
                     template.info_element_small(ID_SPELL_AVAIL_START + ind as u32, ctx, buildings),
@@ -640,20 +688,9 @@ fn construct_buildings_menu(
     let mut construct_box =
         ui::containers::HorizontalBox::new_spaced(25.).to_element_builder(0, ctx);
 
-    let icons = [
-        "/sprites/ui/looking_glass.png",
-        "/sprites/ui/potion.png",
-        "/sprites/ui/mana_add.png",
-    ];
-    let keycodes = [
-        ("B", ggez::winit::event::VirtualKeyCode::B),
-        ("N", ggez::winit::event::VirtualKeyCode::N),
-        ("M", ggez::winit::event::VirtualKeyCode::M),
-    ];
-
-    for i in 0..buildings::BUILDING_TYPES {
-        let info = buildings::get_building_info(i);
-        let build = graphics::Image::from_path(ctx, icons[i])
+    for (i, info) in buildings::get_building_list().iter().enumerate() {
+        let level = buildings.target.get(i).copied().unwrap_or_default();
+        let build = graphics::Image::from_path(ctx, &info.icon)
             .expect("[ERROR/Radish] Missing building sprite.")
             .to_element_builder(ID_BUILDINGS_START + i as u32, ctx)
             .as_shrink()
@@ -661,30 +698,24 @@ fn construct_buildings_menu(
             .with_padding((10., 10., 10., 10.))
             .with_visuals(super::BUTTON_VIS)
             .with_hover_visuals(super::BUTTON_HOVER_VIS)
-            .with_trigger_key(keycodes[i].1)
+            .with_trigger_key(info.key)
             .with_tooltip({
                 let mut text = graphics::Text::new(
-                    graphics::TextFragment::new(
-                        if buildings.target[i] < buildings::BUILDING_MAX_LEVEL as u8 {
-                            format!(
-                                "{} the {}.\n",
-                                if buildings.target[i] == 0 {
-                                    "Construct"
-                                } else {
-                                    "Upgrade"
-                                },
-                                info.name,
-                            )
-                        } else {
-                            format!("{} is fully upgraded.\n", info.name,)
-                        },
-                    )
+                    graphics::TextFragment::new(if level < info.max_level() {
+                        format!(
+                            "{} the {}.\n",
+                            if level == 0 { "Construct" } else { "Upgrade" },
+                            info.name,
+                        )
+                    } else {
+                        format!("{} is fully upgraded.\n", info.name,)
+                    })
                     .color(graphics::Color::from_rgb_u32(PALETTE[6]))
                     .scale(24.),
                 );
 
                 text.add(
-                    TextFragment::new(info.description)
+                    TextFragment::new(&info.description)
                         .scale(20.)
                         .color(graphics::Color::from_rgb_u32(PALETTE[6])),
                 );
@@ -696,12 +727,12 @@ fn construct_buildings_menu(
                 );
 
                 text.add(
-                    TextFragment::new(format!("{}\n", buildings.target[i]))
+                    TextFragment::new(format!("{}\n", level))
                         .scale(20.)
                         .color(graphics::Color::from_rgb_u32(PALETTE[7])),
                 );
 
-                if buildings.target[i] < buildings::BUILDING_MAX_LEVEL as u8 {
+                if let Some(cost) = info.upgrade_cost(level) {
                     text.add(
                         TextFragment::new("Cost: ")
                             .scale(20.)
@@ -709,16 +740,13 @@ fn construct_buildings_menu(
                     );
 
                     text.add(
-                        TextFragment::new(format!(
-                            "{}g\n",
-                            info.level_costs[buildings.target[i] as usize]
-                        ))
-                        .scale(20.)
-                        .color(graphics::Color::from_rgb_u32(PALETTE[7])),
+                        TextFragment::new(format!("{}g\n", cost))
+                            .scale(20.)
+                            .color(graphics::Color::from_rgb_u32(PALETTE[7])),
                     );
 
                     text.add(
-                        TextFragment::new(format!("[{}]\n", keycodes[i].0))
+                        TextFragment::new(format!("[{:?}]\n", info.key))
                             .scale(20.)
                             .color(graphics::Color::from_rgb_u32(PALETTE[6])),
                     );
@@ -732,7 +760,7 @@ fn construct_buildings_menu(
             .build();
 
         let level = graphics::Text::new(
-            graphics::TextFragment::new(format!(" {} ", buildings.target[i]))
+            graphics::TextFragment::new(format!(" {} ", level))
                 .color(graphics::Color::from_rgb_u32(PALETTE[14])),
         )
         .set_scale(24.)
@@ -759,25 +787,51 @@ fn construct_buildings_menu(
         );
     }
 
+    // buildings may be disabled by a mutator
+    let construct_box = if buildings.is_locked() {
+        graphics::Text::new(
+            graphics::TextFragment::new("No buildings can be constructed in this run.")
+                .color(graphics::Color::from_rgb_u32(PALETTE[6])),
+        )
+        .set_scale(24.)
+        .set_font("Retro")
+        .to_owned()
+        .to_element(0, ctx)
+    } else {
+        construct_box.build()
+    };
+
     // Container
     ui::containers::VerticalBox::new_spaced(16.)
         .to_element_builder(ID_WAVE_SUBMENU, ctx)
         .with_child(title)
-        .with_child(construct_box.build())
+        .with_child(construct_box)
         .with_alignment(ui::Alignment::Center, ui::Alignment::Center)
         .as_fill()
         .build()
 }
 
+/// Constructs the announcement of the passed wave and the difficulty it is played on.
+/// In a campaign, the total amount of waves is displayed as well.
 fn construct_wave_announcer(
     ctx: &ggez::Context,
     wave: u32,
+    total: Option<usize>,
+    difficulty: game_state::difficulty::Difficulty,
 ) -> ui::UiElement<game_state::GameMessage> {
     let mut dur = ui::containers::DurationBox::new(
         Duration::from_secs(5),
         graphics::Text::new(
-            graphics::TextFragment::new(format!("Wave {}", wave))
-                .color(graphics::Color::from_rgb_u32(PALETTE[14])),
+            graphics::TextFragment::new(match total {
+                Some(total) => format!("Wave {}/{}", wave, total),
+                None => format!("Wave {}", wave),
+            })
+            .color(graphics::Color::from_rgb_u32(PALETTE[14])),
+        )
+        .add(
+            graphics::TextFragment::new(format!("\n{}", difficulty.name()))
+                .color(graphics::Color::from_rgb_u32(PALETTE[7]))
+                .scale(28.),
         )
         .set_scale(48.)
         .set_font("Retro")
@@ -816,12 +870,13 @@ pub fn sync_ui(
     if let Some(player_ent) = resources.get::<game_state::Entity>();
     if let Ok(mut player) = world.entry_mut(*player_ent);
         then{
+            let bonuses = data.buildings.bonuses();
 
             // sync spell
 
             if let Ok(caster) = player.get_component_mut::<game_state::components::SpellCaster>(){
                 // game sync
-                caster.set_extra_slots(data.buildings.target[buildings::BuildingType::Manawell as usize] as usize);
+                caster.set_extra_slots(bonuses.spell_slots);
                 // ui sync
                 gui.remove_elements(super::game_ui::ID_MANA_SLOT);
                 for i in 0..caster.get_slots() {
@@ -852,7 +907,7 @@ pub fn sync_ui(
 
             if let Ok(control) = player.get_component_mut::<game_state::components::Control>(){
                 // game sync
-                control.move_speed = control.base_speed * (1. + 0.25 * (data.buildings.target[buildings::BuildingType::Watchtower as usize] as f32 - 1.).max(0.));
+                control.move_speed = control.base_speed * (1. + bonuses.move_speed);
             }
         }
     }
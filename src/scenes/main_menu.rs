@@ -1,9 +1,12 @@
 pub mod achievement_menu;
 pub mod credits_menu;
+pub mod custom_game_menu;
 pub mod highscore_menu;
+pub mod mutator_menu;
 pub mod options_menu;
+pub mod statistics_menu;
 
-use std::time::Duration;
+use std::{cell::RefCell, rc::Rc, time::Duration};
 
 use super::game_state;
 use super::BUTTON_HOVER_VIS;
@@ -18,6 +21,8 @@ use crate::PALETTE;
 
 const CAMERA_SPEED: f32 = -60.;
 const REL_TROOP_SPEED: f32 = 14.;
+/// The id of the box listing problems with save files.
+const WARNING_ID: u32 = 20;
 
 /// The main menu greeting the player on startup.
 /// Contains navigation buttons to multiple submenus and allows starting games.
@@ -31,6 +36,8 @@ pub struct MainMenu {
     background_sprites: Vec<MainMenuSprite>,
     /// The current state
     state: MainMenuTransition,
+    /// The config of a run started from the mutator or custom game menu, if any.
+    start_request: Rc<RefCell<Option<game_state::GameConfig>>>,
 }
 
 /// A background sprite in the main menu
@@ -48,12 +55,42 @@ type MainMenuTransition = Option<(Duration, game_state::GameConfig)>;
 
 impl MainMenu {
     pub fn new(ctx: &ggez::Context) -> Result<Self, GameError> {
+        // load save files now, so problems reading them are shown right away
+        game_state::achievements::load_data();
+
         // title
         let title = graphics::Image::from_path(ctx, "/sprites/ui/logo1.png")?
             .to_element_builder(0, ctx)
             .scaled(4., 4.)
             .build();
 
+        // continue
+        let resume = graphics::Text::new(
+            graphics::TextFragment::new("Continue")
+                .color(graphics::Color::from_rgb_u32(PALETTE[6])),
+        )
+        .set_font("Retro")
+        .set_scale(32.)
+        .to_owned()
+        .to_element_builder(10, ctx)
+        .with_trigger_key(ggez::winit::event::VirtualKeyCode::L)
+        .with_visuals(super::BUTTON_VIS)
+        .with_hover_visuals(super::BUTTON_HOVER_VIS)
+        .with_trigger_sound(ggez::audio::Source::new(ctx, "/audio/sounds/ui/blipSelect.wav").ok())
+        .with_tooltip(
+            graphics::Text::new(
+                graphics::TextFragment::new("Resume the run you saved and quit.")
+                    .color(graphics::Color::from_rgb_u32(PALETTE[6])),
+            )
+            .set_scale(24.)
+            .set_font("Retro")
+            .to_owned()
+            .to_element_builder(0, ctx)
+            .with_visuals(super::BUTTON_VIS)
+            .build(),
+        )
+        .build();
+
         // play
         let play = graphics::Text::new(
             graphics::TextFragment::new("Play").color(graphics::Color::from_rgb_u32(PALETTE[6])),
@@ -68,6 +105,131 @@ impl MainMenu {
         .with_trigger_sound(ggez::audio::Source::new(ctx, "/audio/sounds/ui/blipSelect.wav").ok())
         .build();
 
+        // custom game
+        let custom_game = graphics::Text::new(
+            graphics::TextFragment::new("Custom Game")
+                .color(graphics::Color::from_rgb_u32(PALETTE[6])),
+        )
+        .set_font("Retro")
+        .set_scale(32.)
+        .to_owned()
+        .to_element_builder(13, ctx)
+        .with_trigger_key(ggez::winit::event::VirtualKeyCode::G)
+        .with_visuals(super::BUTTON_VIS)
+        .with_hover_visuals(super::BUTTON_HOVER_VIS)
+        .with_trigger_sound(ggez::audio::Source::new(ctx, "/audio/sounds/ui/blipSelect.wav").ok())
+        .with_tooltip(
+            graphics::Text::new(
+                graphics::TextFragment::new(
+                    "Set up your own run, or start one of your saved presets.",
+                )
+                .color(graphics::Color::from_rgb_u32(PALETTE[6])),
+            )
+            .set_scale(24.)
+            .set_font("Retro")
+            .to_owned()
+            .to_element_builder(0, ctx)
+            .with_visuals(super::BUTTON_VIS)
+            .build(),
+        )
+        .build();
+
+        // campaign
+        let campaign_info = match crate::data::from_path::<game_state::Campaign>(
+            crate::paths::data_path(game_state::CAMPAIGN_PATH),
+        ) {
+            Ok(campaign) => {
+                let completions = game_state::achievements::CAMPAIGN_RECORDS.with(|records| {
+                    records
+                        .borrow()
+                        .campaigns
+                        .get(&campaign.name)
+                        .map(|record| record.completions)
+                        .unwrap_or_default()
+                });
+                format!(
+                    "{}\n{}\nCompleted: {}",
+                    campaign.name, campaign.description, completions
+                )
+            }
+            Err(_) => "The campaign could not be loaded.".to_owned(),
+        };
+
+        let campaign = graphics::Text::new(
+            graphics::TextFragment::new("Campaign")
+                .color(graphics::Color::from_rgb_u32(PALETTE[6])),
+        )
+        .set_font("Retro")
+        .set_scale(32.)
+        .to_owned()
+        .to_element_builder(11, ctx)
+        .with_trigger_key(ggez::winit::event::VirtualKeyCode::K)
+        .with_visuals(super::BUTTON_VIS)
+        .with_hover_visuals(super::BUTTON_HOVER_VIS)
+        .with_trigger_sound(ggez::audio::Source::new(ctx, "/audio/sounds/ui/blipSelect.wav").ok())
+        .with_tooltip(
+            graphics::Text::new(
+                graphics::TextFragment::new(campaign_info)
+                    .color(graphics::Color::from_rgb_u32(PALETTE[6])),
+            )
+            .set_scale(24.)
+            .set_bounds(Vec2::new(400., 300.))
+            .set_wrap(true)
+            .set_font("Retro")
+            .to_owned()
+            .to_element_builder(0, ctx)
+            .with_visuals(super::BUTTON_VIS)
+            .build(),
+        )
+        .build();
+
+        // daily challenge
+        let daily_challenge = game_state::daily::DailyChallenge::today();
+        let mut daily_info = format!("Daily Challenge {}", daily_challenge.date);
+        for modifier in &daily_challenge.modifiers {
+            daily_info.push_str(&format!(
+                "\n{}: {}",
+                modifier.name(),
+                modifier.description()
+            ));
+        }
+        if let Some(best) = game_state::achievements::DAILY_HIGHSCORES.with(|scores| {
+            scores
+                .borrow()
+                .day(&daily_challenge.date)
+                .next()
+                .map(|entry| entry.score)
+        }) {
+            daily_info.push_str(&format!("\nBest today: {}", best));
+        }
+
+        let daily = graphics::Text::new(
+            graphics::TextFragment::new("Daily").color(graphics::Color::from_rgb_u32(PALETTE[6])),
+        )
+        .set_font("Retro")
+        .set_scale(32.)
+        .to_owned()
+        .to_element_builder(12, ctx)
+        .with_trigger_key(ggez::winit::event::VirtualKeyCode::T)
+        .with_visuals(super::BUTTON_VIS)
+        .with_hover_visuals(super::BUTTON_HOVER_VIS)
+        .with_trigger_sound(ggez::audio::Source::new(ctx, "/audio/sounds/ui/blipSelect.wav").ok())
+        .with_tooltip(
+            graphics::Text::new(
+                graphics::TextFragment::new(daily_info)
+                    .color(graphics::Color::from_rgb_u32(PALETTE[6])),
+            )
+            .set_scale(24.)
+            .set_bounds(Vec2::new(400., 300.))
+            .set_wrap(true)
+            .set_font("Retro")
+            .to_owned()
+            .to_element_builder(0, ctx)
+            .with_visuals(super::BUTTON_VIS)
+            .build(),
+        )
+        .build();
+
         // advanced start
         let quick_advance = graphics::Text::new(
             graphics::TextFragment::new("Quick Advance")
@@ -110,6 +272,20 @@ impl MainMenu {
         .with_trigger_sound(ggez::audio::Source::new(ctx, "/audio/sounds/ui/blipSelect.wav").ok())
         .build();
 
+        let replay = graphics::Text::new(
+            graphics::TextFragment::new("Last Replay")
+                .color(graphics::Color::from_rgb_u32(PALETTE[6])),
+        )
+        .set_font("Retro")
+        .set_scale(32.)
+        .to_owned()
+        .to_element_builder(9, ctx)
+        .with_trigger_key(ggez::winit::event::VirtualKeyCode::R)
+        .with_visuals(super::BUTTON_VIS)
+        .with_hover_visuals(super::BUTTON_HOVER_VIS)
+        .with_trigger_sound(ggez::audio::Source::new(ctx, "/audio/sounds/ui/blipSelect.wav").ok())
+        .build();
+
         // highscores
 
         let highscores = graphics::Text::new(
@@ -142,6 +318,22 @@ impl MainMenu {
         .with_trigger_sound(ggez::audio::Source::new(ctx, "/audio/sounds/ui/blipSelect.wav").ok())
         .build();
 
+        // statistics
+
+        let statistics = graphics::Text::new(
+            graphics::TextFragment::new("Statistics")
+                .color(graphics::Color::from_rgb_u32(PALETTE[6])),
+        )
+        .set_font("Retro")
+        .set_scale(32.)
+        .to_owned()
+        .to_element_builder(14, ctx)
+        .with_trigger_key(ggez::winit::event::VirtualKeyCode::S)
+        .with_visuals(super::BUTTON_VIS)
+        .with_hover_visuals(super::BUTTON_HOVER_VIS)
+        .with_trigger_sound(ggez::audio::Source::new(ctx, "/audio/sounds/ui/blipSelect.wav").ok())
+        .build();
+
         let options = graphics::Text::new(
             graphics::TextFragment::new("Options").color(graphics::Color::from_rgb_u32(PALETTE[6])),
         )
@@ -184,24 +376,32 @@ impl MainMenu {
         .build();
 
         // Container
-        let menu_box = ui::containers::VerticalBox::new_spaced(25.)
-            .to_element_builder(0, ctx)
-            .with_child(play);
+        let menu_box = ui::containers::VerticalBox::new_spaced(25.).to_element_builder(0, ctx);
 
-        let menu_box =
-            if game_state::achievements::HIGHSCORES.with(|scores| scores.borrow().is_empty()) {
-                menu_box
-            } else {
-                menu_box.with_child(quick_advance)
-            };
+        let menu_box = if game_state::savegame::SaveGame::exists() {
+            menu_box.with_child(resume)
+        } else {
+            menu_box
+        }
+        .with_child(play)
+        .with_child(campaign)
+        .with_child(daily)
+        .with_child(custom_game);
+
+        let menu_box = if game_state::achievements::best_endless_score().is_none() {
+            menu_box
+        } else {
+            menu_box.with_child(quick_advance)
+        };
 
         let menu_box = if cfg!(debug_assertions) {
-            menu_box.with_child(debug)
+            menu_box.with_child(debug).with_child(replay)
         } else {
             menu_box
         }
         .with_child(highscores)
         .with_child(achievements)
+        .with_child(statistics)
         .with_child(options)
         .with_child(credits)
         .with_child(quit)
@@ -277,7 +477,7 @@ impl MainMenu {
         });
 
         // step 3: troops
-        let troop_paths = vec![
+        let troop_paths = [
             "armor",
             "legionnaire",
             "skeleton_basic",
@@ -313,6 +513,7 @@ impl MainMenu {
             music_player,
             background_sprites,
             state: None,
+            start_request: Rc::new(RefCell::new(None)),
         })
     }
 }
@@ -324,47 +525,111 @@ impl scene_manager::Scene for MainMenu {
     ) -> Result<mooeye::scene_manager::SceneSwitch, ggez::GameError> {
         let messages = self.gui.manage_messages(ctx, None);
 
+        // show problems with save files
+
+        let warnings = crate::storage::take_warnings();
+        if !warnings.is_empty() {
+            self.gui.add_element(0, create_warning_box(ctx, &warnings));
+        }
+        if messages.contains(&ui::UiMessage::Triggered(WARNING_ID)) {
+            self.gui.remove_elements(WARNING_ID);
+        }
+
         let mut res = scene_manager::SceneSwitch::None;
 
         match self.state.take() {
             None => {
                 if messages.contains(&ui::UiMessage::Triggered(1)) {
+                    res = scene_manager::SceneSwitch::push(mutator_menu::MutatorMenu::new(
+                        ctx,
+                        self.start_request.clone(),
+                    )?);
+                }
+
+                if messages.contains(&ui::UiMessage::Triggered(13)) {
+                    res = scene_manager::SceneSwitch::push(custom_game_menu::CustomGameMenu::new(
+                        ctx,
+                        self.start_request.clone(),
+                    )?);
+                }
+
+                // start the run configured in the mutator or custom game menu
+                if let Some(config) = self.start_request.take() {
                     for sprite in &mut self.background_sprites {
                         sprite.vel.y -= 128.;
                     }
-                    self.state = Some((Duration::from_secs(4), game_state::GameConfig::default()));
+                    self.state = Some((Duration::from_secs(4), config));
                 }
 
                 if messages.contains(&ui::UiMessage::Triggered(2)) {
                     for sprite in &mut self.background_sprites {
                         sprite.vel.y -= 128.;
                     }
+                    let best = game_state::achievements::best_endless_score();
                     self.state = Some((
                         Duration::from_secs(4),
                         game_state::GameConfig {
-                            starting_gold: game_state::achievements::HIGHSCORES.with(|scores| {
-                                scores
-                                    .borrow()
-                                    .first()
-                                    .map(|(_, score)| *score as i32)
-                                    .unwrap_or_default()
-                            }) / 4,
-                            starting_wave: game_state::achievements::HIGHSCORES.with(|scores| {
-                                scores
-                                    .borrow()
-                                    .first()
-                                    .map(|(wave, _)| *wave)
-                                    .unwrap_or_default()
-                            }) / 2,
+                            starting_gold: best
+                                .as_ref()
+                                .map(|entry| entry.score as i32)
+                                .unwrap_or_default()
+                                / 4,
+                            starting_wave: best.map(|entry| entry.wave).unwrap_or_default() / 2,
                             ..Default::default()
                         },
                     ));
                 }
 
+                if messages.contains(&ui::UiMessage::Triggered(11)) {
+                    for sprite in &mut self.background_sprites {
+                        sprite.vel.y -= 128.;
+                    }
+                    self.state = Some((Duration::from_secs(4), game_state::GameConfig::campaign()));
+                }
+
+                if messages.contains(&ui::UiMessage::Triggered(12)) {
+                    for sprite in &mut self.background_sprites {
+                        sprite.vel.y -= 128.;
+                    }
+                    self.state = Some((Duration::from_secs(4), game_state::GameConfig::daily()));
+                }
+
                 if messages.contains(&ui::UiMessage::Triggered(3)) {
                     self.state = Some((Duration::ZERO, game_state::GameConfig::debug()));
                 }
 
+                if messages.contains(&ui::UiMessage::Triggered(10)) {
+                    match crate::storage::load::<game_state::savegame::SaveGame>(
+                        crate::paths::save_path(game_state::savegame::SAVEGAME_PATH),
+                        "saved game",
+                    ) {
+                        Some(save) => {
+                            // a save game can only be resumed once
+                            if let Err(err) = crate::storage::remove(crate::paths::save_path(
+                                game_state::savegame::SAVEGAME_PATH,
+                            )) {
+                                println!("[ERROR/Radish] Could not remove save game: {}", err);
+                            }
+                            self.music_player.stop(ctx);
+                            res = mooeye::scene_manager::SceneSwitch::replace(
+                                game_state::GameState::from_save(ctx, save)?,
+                                1,
+                            );
+                        }
+                        None => println!("[ERROR/Radish] Could not load save game."),
+                    }
+                }
+
+                if messages.contains(&ui::UiMessage::Triggered(9)) {
+                    if let Some(replay) = game_state::replay::Replay::latest() {
+                        self.music_player.stop(ctx);
+                        res = mooeye::scene_manager::SceneSwitch::replace(
+                            game_state::GameState::from_replay(ctx, replay)?,
+                            1,
+                        );
+                    }
+                }
+
                 if messages.contains(&ui::UiMessage::Triggered(4)) {
                     res =
                         scene_manager::SceneSwitch::push(highscore_menu::HighscoreMenu::new(ctx)?);
@@ -376,6 +641,12 @@ impl scene_manager::Scene for MainMenu {
                     )?);
                 }
 
+                if messages.contains(&ui::UiMessage::Triggered(14)) {
+                    res = scene_manager::SceneSwitch::push(statistics_menu::StatisticsMenu::new(
+                        ctx,
+                    )?);
+                }
+
                 if messages.contains(&ui::UiMessage::Triggered(6)) {
                     res = scene_manager::SceneSwitch::push(options_menu::OptionsMenu::new(ctx)?);
                 }
@@ -481,3 +752,39 @@ impl scene_manager::Scene for MainMenu {
         Ok(())
     }
 }
+
+/// Creates a box listing the passed warnings about save files, that is dismissed when clicked.
+fn create_warning_box(ctx: &ggez::Context, warnings: &[String]) -> ui::UiElement<()> {
+    let mut text = graphics::Text::new(
+        graphics::TextFragment::new("Some of your save files could not be read:")
+            .color(graphics::Color::from_rgb_u32(PALETTE[8])),
+    );
+    for warning in warnings {
+        text.add(
+            graphics::TextFragment::new(format!("\n{}", warning))
+                .color(graphics::Color::from_rgb_u32(PALETTE[6]))
+                .scale(20.),
+        );
+    }
+
+    text.set_font("Retro")
+        .set_scale(24.)
+        .to_owned()
+        .to_element_builder(WARNING_ID, ctx)
+        .with_visuals(BUTTON_VIS)
+        .with_hover_visuals(BUTTON_HOVER_VIS)
+        .with_tooltip(
+            graphics::Text::new(
+                graphics::TextFragment::new("Click to dismiss.")
+                    .color(graphics::Color::from_rgb_u32(PALETTE[6])),
+            )
+            .set_scale(24.)
+            .set_font("Retro")
+            .to_owned()
+            .to_element_builder(0, ctx)
+            .with_visuals(BUTTON_VIS)
+            .build(),
+        )
+        .with_padding((0., 25., 0., 0.))
+        .build()
+}
@@ -21,10 +21,13 @@ impl AchievementMenu {
         .to_owned()
         .to_element(0, ctx);
 
-        let a_list =
-            achievements::AchievementSet::load(ctx, achievements::AchievementProgressSource::Cache);
+        let a_list = achievements::AchievementSet::load(
+            Some(ctx),
+            achievements::AchievementProgressSource::Cache,
+        );
 
-        let mut achievements = ui::containers::GridBox::new(4, (a_list.list.len() - 1) / 4 + 1);
+        let mut achievements =
+            ui::containers::GridBox::new(4, a_list.list.len().saturating_sub(1) / 4 + 1);
         for (index, ach) in a_list.list.iter().enumerate() {
             achievements.add(ach.info_element_small(ctx), index % 4, index / 4)?;
         }
@@ -89,7 +92,7 @@ impl scene_manager::Scene for AchievementMenu {
 
         if messages.contains(&ui::UiMessage::Triggered(2)) {
             let mut a_list = achievements::AchievementSet::load(
-                ctx,
+                Some(ctx),
                 achievements::AchievementProgressSource::Cache,
             );
             for achievement in a_list.list.iter_mut() {
@@ -0,0 +1,483 @@
+use std::{cell::RefCell, rc::Rc};
+
+use ggez::winit::event::VirtualKeyCode;
+use ggez::{graphics, GameError};
+use mooeye::{scene_manager, ui, ui::UiContent};
+
+use crate::PALETTE;
+
+use super::super::game_state;
+use super::options_menu::create_sound_adjuster;
+
+const SETTINGS_CONTAINER_ID: u32 = 10;
+const SETTINGS_ID: u32 = 20;
+const PRESETS_CONTAINER_ID: u32 = 30;
+const PRESETS_ID: u32 = 40;
+const NAME_CONTAINER_ID: u32 = 50;
+const NAME_ID: u32 = 60;
+/// The first id of the adjusters. Each setting uses ten ids, starting at this id plus ten times its index.
+const SETTING_IDS: u32 = 100;
+/// The first id of the preset buttons.
+const PRESET_IDS: u32 = 300;
+
+/// The maximum amount of presets listed.
+const MAX_PRESETS: usize = 8;
+/// The maximum length of a preset name.
+const MAX_NAME_LENGTH: usize = 16;
+
+/// A value of the game config editable in the custom game menu.
+#[derive(Clone, Copy)]
+enum Setting {
+    StartingWave,
+    StartingGold,
+    CityHealth,
+    BaseSlots,
+    BaseSpeed,
+    BaseCredits,
+    WaveCredits,
+    /// The spell in the passed slot.
+    Spell(usize),
+}
+
+impl Setting {
+    /// All settings, in the order they are displayed in.
+    const ALL: [Self; 11] = [
+        Self::StartingWave,
+        Self::StartingGold,
+        Self::CityHealth,
+        Self::BaseSlots,
+        Self::BaseSpeed,
+        Self::BaseCredits,
+        Self::WaveCredits,
+        Self::Spell(0),
+        Self::Spell(1),
+        Self::Spell(2),
+        Self::Spell(3),
+    ];
+
+    /// Returns the label displayed next to this setting.
+    fn label(&self) -> String {
+        match self {
+            Self::StartingWave => "Starting Wave".to_owned(),
+            Self::StartingGold => "Starting Gold".to_owned(),
+            Self::CityHealth => "City Health".to_owned(),
+            Self::BaseSlots => "Spell Slots".to_owned(),
+            Self::BaseSpeed => "Speed".to_owned(),
+            Self::BaseCredits => "Base Credits".to_owned(),
+            Self::WaveCredits => "Wave Credits".to_owned(),
+            Self::Spell(slot) => format!("Spell {}", slot + 1),
+        }
+    }
+
+    /// Returns the current value of this setting in the passed config, formatted for display.
+    fn value(&self, config: &game_state::GameConfig, spell_names: &[String]) -> String {
+        match self {
+            Self::StartingWave => config.starting_wave.to_string(),
+            Self::StartingGold => config.starting_gold.to_string(),
+            Self::CityHealth => config.starting_city_health.to_string(),
+            Self::BaseSlots => config.base_slots.to_string(),
+            Self::BaseSpeed => format!("{:.0}", config.base_speed),
+            Self::BaseCredits => format!("{:.1}", config.base_credits),
+            Self::WaveCredits => format!("{:.1}", config.wave_credits),
+            Self::Spell(slot) => match config.base_spells[*slot] {
+                0 => "None".to_owned(),
+                index => spell_names
+                    .get(index - 1)
+                    .cloned()
+                    .unwrap_or_else(|| "None".to_owned()),
+            },
+        }
+    }
+
+    /// Changes this setting in the passed config by the passed amount of steps.
+    /// Large steps change numbers by a larger amount, while spells are always cycled one by one.
+    fn adjust(
+        &self,
+        config: &mut game_state::GameConfig,
+        steps: i32,
+        large: bool,
+        spell_count: usize,
+    ) {
+        let factor = |small: f32, big: f32| steps as f32 * if large { big } else { small };
+        match self {
+            Self::StartingWave => {
+                config.starting_wave =
+                    (config.starting_wave as i32 + factor(1., 5.) as i32).max(1) as u32
+            }
+            Self::StartingGold => {
+                config.starting_gold = (config.starting_gold + factor(10., 100.) as i32).max(0)
+            }
+            Self::CityHealth => {
+                config.starting_city_health =
+                    (config.starting_city_health + factor(1., 5.) as i32).max(1)
+            }
+            Self::BaseSlots => {
+                config.base_slots =
+                    (config.base_slots as i32 + factor(1., 2.) as i32).clamp(1, 12) as usize
+            }
+            Self::BaseSpeed => {
+                config.base_speed = (config.base_speed + factor(5., 25.)).clamp(25., 500.)
+            }
+            Self::BaseCredits => {
+                config.base_credits = (config.base_credits + factor(1., 5.)).max(0.)
+            }
+            Self::WaveCredits => {
+                config.wave_credits = (config.wave_credits + factor(0.5, 2.)).max(0.)
+            }
+            Self::Spell(slot) => {
+                // cycle through all spells and the empty slot
+                let options = spell_count as i32 + 1;
+                config.base_spells[*slot] =
+                    (config.base_spells[*slot] as i32 + steps).rem_euclid(options) as usize
+            }
+        }
+    }
+}
+
+/// A menu allowing the player to edit the config of a run, save it as a named preset, load presets and start them.
+pub struct CustomGameMenu {
+    gui: ui::UiElement<()>,
+    /// The config currently edited.
+    config: game_state::GameConfig,
+    /// The name the current config is saved as.
+    name: String,
+    /// The presets currently saved.
+    presets: Vec<(String, game_state::GameConfig)>,
+    /// The names of all spells, in the order of their indices.
+    spell_names: Vec<String>,
+    /// The config of the run to start is written here once the player starts the run.
+    start_request: Rc<RefCell<Option<game_state::GameConfig>>>,
+}
+
+impl CustomGameMenu {
+    pub fn new(
+        ctx: &ggez::Context,
+        start_request: Rc<RefCell<Option<game_state::GameConfig>>>,
+    ) -> Result<Self, GameError> {
+        let config = game_state::GameConfig {
+            tutorial: false,
+            ..Default::default()
+        };
+        let name = "custom".to_owned();
+        let presets = game_state::GameConfig::load_presets();
+        let spell_names = game_state::spell_names();
+
+        // title
+
+        let title = graphics::Text::new(
+            graphics::TextFragment::new("Custom Game")
+                .color(graphics::Color::from_rgb_u32(PALETTE[8])),
+        )
+        .set_font("Retro")
+        .set_scale(48.)
+        .to_owned()
+        .to_element(0, ctx);
+
+        let presets_title = graphics::Text::new(
+            graphics::TextFragment::new("Presets").color(graphics::Color::from_rgb_u32(PALETTE[7])),
+        )
+        .set_font("Retro")
+        .set_scale(32.)
+        .to_owned()
+        .to_element(0, ctx);
+
+        let save = graphics::Text::new(
+            graphics::TextFragment::new("Save Preset")
+                .color(graphics::Color::from_rgb_u32(PALETTE[6])),
+        )
+        .set_font("Retro")
+        .set_scale(28.)
+        .to_owned()
+        .to_element_builder(1, ctx)
+        .with_visuals(super::BUTTON_VIS)
+        .with_hover_visuals(super::BUTTON_HOVER_VIS)
+        .with_trigger_sound(ggez::audio::Source::new(ctx, "/audio/sounds/ui/blipSelect.wav").ok())
+        .with_tooltip(
+            graphics::Text::new(
+                graphics::TextFragment::new("Type to change the name of the preset.")
+                    .color(graphics::Color::from_rgb_u32(PALETTE[6])),
+            )
+            .set_scale(24.)
+            .set_font("Retro")
+            .to_owned()
+            .to_element_builder(0, ctx)
+            .with_visuals(super::BUTTON_VIS)
+            .build(),
+        )
+        .build();
+
+        let start = graphics::Text::new(
+            graphics::TextFragment::new("Start").color(graphics::Color::from_rgb_u32(PALETTE[6])),
+        )
+        .set_font("Retro")
+        .set_scale(32.)
+        .to_owned()
+        .to_element_builder(2, ctx)
+        .with_trigger_key(VirtualKeyCode::Return)
+        .with_visuals(super::BUTTON_VIS)
+        .with_hover_visuals(super::BUTTON_HOVER_VIS)
+        .with_trigger_sound(ggez::audio::Source::new(ctx, "/audio/sounds/ui/blipSelect.wav").ok())
+        .build();
+
+        // letter keys are used to type the preset name, so the menu is closed via escape
+        let back = graphics::Text::new(
+            graphics::TextFragment::new("Close").color(graphics::Color::from_rgb_u32(PALETTE[6])),
+        )
+        .set_font("Retro")
+        .set_scale(32.)
+        .to_owned()
+        .to_element_builder(3, ctx)
+        .with_trigger_key(VirtualKeyCode::Escape)
+        .with_visuals(super::BUTTON_VIS)
+        .with_hover_visuals(super::BUTTON_HOVER_VIS)
+        .with_trigger_sound(ggez::audio::Source::new(ctx, "/audio/sounds/ui/blipSelect.wav").ok())
+        .build();
+
+        // Container
+
+        let preset_box = ui::containers::VerticalBox::new_spaced(15.)
+            .to_element_builder(0, ctx)
+            .with_child(presets_title)
+            .with_child(
+                ui::containers::StackBox::new()
+                    .to_element_builder(PRESETS_CONTAINER_ID, ctx)
+                    .with_child(create_preset_list(ctx, &presets))
+                    .with_wrapper_layout(ui::Layout::default())
+                    .build(),
+            )
+            .with_child(
+                ui::containers::StackBox::new()
+                    .to_element_builder(NAME_CONTAINER_ID, ctx)
+                    .with_child(create_name_display(ctx, &name))
+                    .with_wrapper_layout(ui::Layout::default())
+                    .build(),
+            )
+            .with_child(save)
+            .with_child(
+                ().to_element_builder(0, ctx)
+                    .with_size(None, ui::Size::Fixed(20.))
+                    .build(),
+            )
+            .with_child(start)
+            .with_child(back)
+            .build();
+
+        let menu_box = ui::containers::VerticalBox::new_spaced(25.)
+            .to_element_builder(0, ctx)
+            .with_child(title)
+            .with_child(
+                ui::containers::HorizontalBox::new_spaced(40.)
+                    .to_element_builder(0, ctx)
+                    .with_child(
+                        ui::containers::StackBox::new()
+                            .to_element_builder(SETTINGS_CONTAINER_ID, ctx)
+                            .with_child(create_settings(ctx, &config, &spell_names))
+                            .with_wrapper_layout(ui::Layout::default())
+                            .build(),
+                    )
+                    .with_child(preset_box)
+                    .build(),
+            )
+            .with_visuals(super::BUTTON_VIS)
+            .with_alignment(ui::Alignment::Center, ui::Alignment::Center)
+            .with_padding((25., 25., 25., 25.))
+            .build();
+
+        Ok(Self {
+            gui: menu_box,
+            config,
+            name,
+            presets,
+            spell_names,
+            start_request,
+        })
+    }
+}
+
+impl scene_manager::Scene for CustomGameMenu {
+    fn update(
+        &mut self,
+        ctx: &mut ggez::Context,
+    ) -> Result<mooeye::scene_manager::SceneSwitch, GameError> {
+        let messages = self.gui.manage_messages(ctx, None);
+
+        // adjust settings
+
+        let mut rebuild_settings = false;
+
+        for (index, setting) in Setting::ALL.iter().enumerate() {
+            let id_start = SETTING_IDS + 10 * index as u32;
+            for (offset, steps, large) in
+                [(1, -1, true), (2, -1, false), (3, 1, false), (4, 1, true)]
+            {
+                if messages.contains(&ui::UiMessage::Triggered(id_start + offset)) {
+                    setting.adjust(&mut self.config, steps, large, self.spell_names.len());
+                    rebuild_settings = true;
+                }
+            }
+        }
+
+        // load presets
+
+        for (index, (name, config)) in self.presets.iter().enumerate().take(MAX_PRESETS) {
+            if messages.contains(&ui::UiMessage::Triggered(PRESET_IDS + index as u32)) {
+                self.config = config.clone();
+                self.name = name.clone();
+                rebuild_settings = true;
+                self.gui.remove_elements(NAME_ID);
+                self.gui
+                    .add_element(NAME_CONTAINER_ID, create_name_display(ctx, &self.name));
+            }
+        }
+
+        if rebuild_settings {
+            self.gui.remove_elements(SETTINGS_ID);
+            self.gui.add_element(
+                SETTINGS_CONTAINER_ID,
+                create_settings(ctx, &self.config, &self.spell_names),
+            );
+        }
+
+        // type the preset name
+
+        if super::super::type_name(ctx, &mut self.name, MAX_NAME_LENGTH) {
+            self.gui.remove_elements(NAME_ID);
+            self.gui
+                .add_element(NAME_CONTAINER_ID, create_name_display(ctx, &self.name));
+        }
+
+        // save preset
+
+        if messages.contains(&ui::UiMessage::Triggered(1)) && !self.name.is_empty() {
+            match self.config.save_preset(&self.name) {
+                Ok(()) => {
+                    self.presets = game_state::GameConfig::load_presets();
+                    self.gui.remove_elements(PRESETS_ID);
+                    self.gui
+                        .add_element(PRESETS_CONTAINER_ID, create_preset_list(ctx, &self.presets));
+                }
+                Err(e) => println!("[ERROR/Radish] Could not save preset {}: {}", self.name, e),
+            }
+        }
+
+        // start the run
+
+        if messages.contains(&ui::UiMessage::Triggered(2)) {
+            *self.start_request.borrow_mut() = Some(game_state::GameConfig {
+                mode: game_state::RunMode::Custom,
+                ..self.config.clone()
+            });
+            return Ok(scene_manager::SceneSwitch::Pop(1));
+        }
+
+        if messages.contains(&ui::UiMessage::Triggered(3)) {
+            Ok(scene_manager::SceneSwitch::Pop(1))
+        } else {
+            Ok(scene_manager::SceneSwitch::None)
+        }
+    }
+
+    fn draw(&mut self, ctx: &mut ggez::Context, mouse_listen: bool) -> Result<(), GameError> {
+        let mut canvas = graphics::Canvas::from_frame(ctx, None);
+        canvas.set_sampler(graphics::Sampler::nearest_clamp());
+
+        self.gui.draw_to_screen(ctx, &mut canvas, mouse_listen);
+
+        canvas.finish(ctx)?;
+        Ok(())
+    }
+}
+
+/// Creates a labeled adjuster for every setting, displaying its value in the passed config.
+fn create_settings(
+    ctx: &ggez::Context,
+    config: &game_state::GameConfig,
+    spell_names: &[String],
+) -> ui::UiElement<()> {
+    let mut settings =
+        ui::containers::VerticalBox::new_spaced(10.).to_element_builder(SETTINGS_ID, ctx);
+
+    for (index, setting) in Setting::ALL.iter().enumerate() {
+        settings = settings.with_child(
+            ui::containers::HorizontalBox::new_spaced(15.)
+                .to_element_builder(0, ctx)
+                .with_child(
+                    graphics::Text::new(
+                        graphics::TextFragment::new(setting.label())
+                            .color(graphics::Color::from_rgb_u32(PALETTE[6])),
+                    )
+                    .set_font("Retro")
+                    .set_scale(28.)
+                    .to_owned()
+                    .to_element_builder(0, ctx)
+                    .with_size(ui::Size::Fixed(200.), None)
+                    .build(),
+                )
+                .with_child(create_sound_adjuster(
+                    ctx,
+                    SETTING_IDS + 10 * index as u32,
+                    setting.value(config, spell_names),
+                ))
+                .with_size(ui::Size::Fixed(560.), None)
+                .build(),
+        );
+    }
+
+    settings.build()
+}
+
+/// Creates a list of buttons loading the passed presets.
+fn create_preset_list(
+    ctx: &ggez::Context,
+    presets: &[(String, game_state::GameConfig)],
+) -> ui::UiElement<()> {
+    let mut list = ui::containers::VerticalBox::new_spaced(10.).to_element_builder(PRESETS_ID, ctx);
+
+    if presets.is_empty() {
+        list = list.with_child(
+            graphics::Text::new(
+                graphics::TextFragment::new("No presets saved yet.")
+                    .color(graphics::Color::from_rgb_u32(PALETTE[7])),
+            )
+            .set_font("Retro")
+            .set_scale(24.)
+            .to_owned()
+            .to_element(0, ctx),
+        );
+    }
+
+    for (index, (name, _)) in presets.iter().enumerate().take(MAX_PRESETS) {
+        list = list.with_child(
+            graphics::Text::new(
+                graphics::TextFragment::new(name).color(graphics::Color::from_rgb_u32(PALETTE[6])),
+            )
+            .set_font("Retro")
+            .set_scale(24.)
+            .to_owned()
+            .to_element_builder(PRESET_IDS + index as u32, ctx)
+            .with_visuals(super::BUTTON_VIS)
+            .with_hover_visuals(super::BUTTON_HOVER_VIS)
+            .with_trigger_sound(
+                ggez::audio::Source::new(ctx, "/audio/sounds/ui/blipSelect.wav").ok(),
+            )
+            .build(),
+        );
+    }
+
+    list.build()
+}
+
+/// Creates a display of the name the edited config is saved as.
+fn create_name_display(ctx: &ggez::Context, name: &str) -> ui::UiElement<()> {
+    graphics::Text::new(
+        graphics::TextFragment::new("Name: ").color(graphics::Color::from_rgb_u32(PALETTE[7])),
+    )
+    .add(
+        graphics::TextFragment::new(format!("{}_", name))
+            .color(graphics::Color::from_rgb_u32(PALETTE[8])),
+    )
+    .set_font("Retro")
+    .set_scale(24.)
+    .to_owned()
+    .to_element(NAME_ID, ctx)
+}
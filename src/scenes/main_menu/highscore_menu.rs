@@ -3,10 +3,29 @@ use mooeye::{scene_manager, ui, ui::UiContainer, ui::UiContent};
 
 use crate::PALETTE;
 
-use super::super::game_state;
+use super::super::game_state::{self, achievements::Highscore, RunMode};
 
+const FILTER_ID: u32 = 3;
+const DELETE_ID: u32 = 4;
+const FILTER_CONTAINER_ID: u32 = 5;
+const LIST_CONTAINER_ID: u32 = 10;
+const LIST_ID: u32 = 20;
+const DETAILS_CONTAINER_ID: u32 = 30;
+const DETAILS_ID: u32 = 40;
+/// The first id of the entry buttons.
+const ENTRY_IDS: u32 = 100;
+
+/// The maximum amount of entries listed.
+const MAX_ENTRIES: usize = 10;
+
+/// A menu listing the highscores, optionally only those of a certain mode.
+/// Selecting an entry shows the details of its run and allows deleting it.
 pub struct HighscoreMenu {
     gui: ui::UiElement<()>,
+    /// The mode of the entries listed, or None if all entries are listed.
+    filter: Option<RunMode>,
+    /// The index of the selected entry within the highscore list, if any.
+    selected: Option<usize>,
 }
 
 impl HighscoreMenu {
@@ -22,26 +41,57 @@ impl HighscoreMenu {
         .to_owned()
         .to_element(0, ctx);
 
-        // Score display
+        let filter = ui::containers::StackBox::new()
+            .to_element_builder(FILTER_CONTAINER_ID, ctx)
+            .with_child(create_filter_button(ctx, None))
+            .with_wrapper_layout(ui::Layout::default())
+            .build();
 
-        let mut highscore_disp = graphics::Text::default();
+        // Score display
 
-        game_state::achievements::HIGHSCORES.with(|scores| {
-            for (index, (_, score)) in scores.borrow().iter().enumerate().take(10) {
-                highscore_disp.add(
-                    graphics::TextFragment::new(format!("{:02}.{:>7}\n", index + 1, *score))
-                        .color(graphics::Color::from_rgb_u32(PALETTE[6]))
-                        .scale(32.),
-                );
-            }
-        });
+        let scores = ui::containers::HorizontalBox::new_spaced(25.)
+            .to_element_builder(0, ctx)
+            .with_child(
+                ui::containers::StackBox::new()
+                    .to_element_builder(LIST_CONTAINER_ID, ctx)
+                    .with_child(create_entry_list(ctx, None, None))
+                    .with_wrapper_layout(ui::Layout::default())
+                    .build(),
+            )
+            .with_child(
+                ui::containers::StackBox::new()
+                    .to_element_builder(DETAILS_CONTAINER_ID, ctx)
+                    .with_child(create_details(ctx, None))
+                    .with_wrapper_layout(ui::Layout::default())
+                    .build(),
+            )
+            .build();
 
-        let highscore_disp = highscore_disp
-            .set_font("Retro_M")
+        let delete = graphics::Text::new(
+            graphics::TextFragment::new("Delete Entry")
+                .color(graphics::Color::from_rgb_u32(PALETTE[6])),
+        )
+        .set_font("Retro")
+        .set_scale(28.)
+        .to_owned()
+        .to_element_builder(DELETE_ID, ctx)
+        .with_tooltip(
+            graphics::Text::new(
+                graphics::TextFragment::new("Removes the selected entry from the highscore list.")
+                    .color(graphics::Color::from_rgb_u32(PALETTE[6])),
+            )
+            .set_scale(24.)
+            .set_font("Retro")
             .to_owned()
             .to_element_builder(0, ctx)
-            .with_alignment(ui::Alignment::Center, ui::Alignment::Min)
-            .build();
+            .with_visuals(super::BUTTON_VIS)
+            .build(),
+        )
+        .with_trigger_key(ggez::winit::event::VirtualKeyCode::Delete)
+        .with_visuals(super::BUTTON_VIS)
+        .with_hover_visuals(super::BUTTON_HOVER_VIS)
+        .with_trigger_sound(ggez::audio::Source::new(ctx, "/audio/sounds/ui/blipSelect.wav").ok())
+        .build();
 
         let reset_scores = graphics::Text::new(
             graphics::TextFragment::new("Reset Highscores")
@@ -86,21 +136,25 @@ impl HighscoreMenu {
 
         // Container
 
-        let mut hs_box = ui::containers::VerticalBox::new();
-        hs_box.add(title);
-        hs_box.add(highscore_disp);
-        hs_box.add(reset_scores);
-        hs_box.add(back);
-        hs_box.spacing = 25.;
-        let credits_box = hs_box
+        let credits_box = ui::containers::VerticalBox::new_spaced(25.)
             .to_element_builder(0, ctx)
+            .with_child(title)
+            .with_child(filter)
+            .with_child(scores)
+            .with_child(delete)
+            .with_child(reset_scores)
+            .with_child(back)
             .with_visuals(super::BUTTON_VIS)
             .with_alignment(ui::Alignment::Max, ui::Alignment::Center)
             .with_offset(-25., 0.)
             .with_padding((25., 25., 25., 25.))
             .build();
 
-        Ok(Self { gui: credits_box })
+        Ok(Self {
+            gui: credits_box,
+            filter: None,
+            selected: None,
+        })
     }
 }
 
@@ -111,9 +165,76 @@ impl scene_manager::Scene for HighscoreMenu {
     ) -> Result<scene_manager::SceneSwitch, ggez::GameError> {
         let messages = self.gui.manage_messages(ctx, None);
 
+        let mut rebuild = false;
+
+        // cycle through the modes
+
+        if messages.contains(&ui::UiMessage::Triggered(FILTER_ID)) {
+            self.filter = match self.filter {
+                None => RunMode::ALL.first().copied(),
+                Some(mode) => RunMode::ALL
+                    .iter()
+                    .skip_while(|other| **other != mode)
+                    .nth(1)
+                    .copied(),
+            };
+            self.selected = None;
+            self.gui.remove_elements(FILTER_ID);
+            self.gui
+                .add_element(FILTER_CONTAINER_ID, create_filter_button(ctx, self.filter));
+            rebuild = true;
+        }
+
+        // select an entry
+
+        for index in 0..game_state::achievements::HIGHSCORES.with(|scores| scores.borrow().len()) {
+            if messages.contains(&ui::UiMessage::Triggered(ENTRY_IDS + index as u32)) {
+                self.selected = Some(index);
+                rebuild = true;
+            }
+        }
+
+        // delete the selected entry
+
+        if messages.contains(&ui::UiMessage::Triggered(DELETE_ID)) {
+            if let Some(index) = self.selected.take() {
+                game_state::achievements::HIGHSCORES.with(|scores| {
+                    let mut scores = scores.borrow_mut();
+                    if index < scores.len() {
+                        scores.remove(index);
+                    }
+                });
+                rebuild = true;
+            }
+        }
+
         if messages.contains(&ui::UiMessage::Triggered(1)) {
             // delete highscores
-            std::fs::write("./data/highscores.toml", "")?;
+            game_state::achievements::HIGHSCORES.with(|scores| scores.borrow_mut().clear());
+            if let Err(e) =
+                crate::storage::write_atomic(crate::paths::save_path("highscores.toml"), "")
+            {
+                println!("[ERROR/Radish] Could not delete highscores: {}", e);
+            }
+            self.selected = None;
+            rebuild = true;
+        }
+
+        if rebuild {
+            self.gui.remove_elements(LIST_ID);
+            self.gui.add_element(
+                LIST_CONTAINER_ID,
+                create_entry_list(ctx, self.filter, self.selected),
+            );
+            self.gui.remove_elements(DETAILS_ID);
+            let details = game_state::achievements::HIGHSCORES.with(|scores| {
+                create_details(
+                    ctx,
+                    self.selected
+                        .and_then(|index| scores.borrow().get(index).cloned()),
+                )
+            });
+            self.gui.add_element(DETAILS_CONTAINER_ID, details);
         }
 
         if messages.contains(&ui::UiMessage::Triggered(2)) {
@@ -133,3 +254,166 @@ impl scene_manager::Scene for HighscoreMenu {
         Ok(())
     }
 }
+
+/// Creates the button cycling through the modes the highscores can be filtered by, labeled with the passed filter.
+fn create_filter_button(ctx: &ggez::Context, filter: Option<RunMode>) -> ui::UiElement<()> {
+    graphics::Text::new(
+        graphics::TextFragment::new(filter_name(filter))
+            .color(graphics::Color::from_rgb_u32(PALETTE[6])),
+    )
+    .set_font("Retro")
+    .set_scale(28.)
+    .to_owned()
+    .to_element_builder(FILTER_ID, ctx)
+    .with_tooltip(
+        graphics::Text::new(
+            graphics::TextFragment::new("Only list the scores of regular or custom runs.")
+                .color(graphics::Color::from_rgb_u32(PALETTE[6])),
+        )
+        .set_scale(24.)
+        .set_font("Retro")
+        .to_owned()
+        .to_element_builder(0, ctx)
+        .with_visuals(super::BUTTON_VIS)
+        .build(),
+    )
+    .with_trigger_key(ggez::winit::event::VirtualKeyCode::F)
+    .with_visuals(super::BUTTON_VIS)
+    .with_hover_visuals(super::BUTTON_HOVER_VIS)
+    .with_trigger_sound(ggez::audio::Source::new(ctx, "/audio/sounds/ui/blipSelect.wav").ok())
+    .build()
+}
+
+/// Returns the label of the filter button for the passed filter.
+fn filter_name(filter: Option<RunMode>) -> String {
+    format!("Mode: {}", filter.map(|mode| mode.name()).unwrap_or("All"))
+}
+
+/// Creates a list of the best entries of the highscore list with the passed mode, or all entries if no mode is passed.
+/// Each entry can be clicked to select it, highlighting it.
+fn create_entry_list(
+    ctx: &ggez::Context,
+    filter: Option<RunMode>,
+    selected: Option<usize>,
+) -> ui::UiElement<()> {
+    let mut list = ui::containers::VerticalBox::new_spaced(5.);
+
+    game_state::achievements::HIGHSCORES.with(|scores| {
+        for (rank, (index, entry)) in scores
+            .borrow()
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| filter.is_none_or(|mode| entry.mode == mode))
+            .take(MAX_ENTRIES)
+            .enumerate()
+        {
+            list.add(
+                graphics::Text::new(
+                    graphics::TextFragment::new(format!(
+                        "{:02}.{:>7} {}",
+                        rank + 1,
+                        entry.score,
+                        entry.name
+                    ))
+                    .color(graphics::Color::from_rgb_u32(if Some(index) == selected {
+                        PALETTE[8]
+                    } else {
+                        PALETTE[6]
+                    }))
+                    .scale(28.),
+                )
+                // list the difficulty and mutators the score was achieved with
+                .add(
+                    graphics::TextFragment::new(format!(
+                        "\n    {}",
+                        std::iter::once(entry.difficulty.name())
+                            .chain(entry.mutators.iter().map(|mutator| mutator.name()))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ))
+                    .color(graphics::Color::from_rgb_u32(PALETTE[7]))
+                    .scale(18.),
+                )
+                .set_font("Retro_M")
+                .to_owned()
+                .to_element_builder(ENTRY_IDS + index as u32, ctx)
+                .with_hover_visuals(super::BUTTON_HOVER_VIS)
+                .build(),
+            );
+        }
+    });
+
+    list.to_element(LIST_ID, ctx)
+}
+
+/// Creates a text describing the run of the passed entry, or asking the player to select an entry if none is passed.
+fn create_details(ctx: &ggez::Context, entry: Option<Highscore>) -> ui::UiElement<()> {
+    let details = match entry {
+        None => "Select an entry\nto see the details\nof its run.".to_owned(),
+        Some(entry) => {
+            let mut details = format!(
+                "{}\nDate: {}\nMode: {}\nDifficulty: {}\nWave: {}\nScore: {}",
+                if entry.name.is_empty() {
+                    "Unnamed"
+                } else {
+                    &entry.name
+                },
+                if entry.date.is_empty() {
+                    "Unknown"
+                } else {
+                    &entry.date
+                },
+                entry.mode.name(),
+                entry.difficulty.name(),
+                entry.wave,
+                entry.score,
+            );
+            if !entry.mutators.is_empty() {
+                details.push_str(&format!(
+                    "\nMutators: {}",
+                    entry
+                        .mutators
+                        .iter()
+                        .map(|mutator| mutator.name())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+            match entry.stats {
+                Some(stats) => details.push_str(&format!(
+                    "\nDuration: {}\nSeed: {}\nKills: {} ({} elite, {} bosses)",
+                    stats.format_duration(),
+                    stats.seed,
+                    stats.kills,
+                    stats.elite_kills,
+                    stats.boss_kills
+                )),
+                None => details.push_str("\nNo further details\nwere recorded."),
+            }
+            if !entry.spells.is_empty() {
+                details.push_str("\nSpells:");
+                for spell in entry.spells.iter() {
+                    details.push_str(&format!("\n  {}", spell));
+                }
+            }
+            if !entry.buildings.is_empty() {
+                details.push_str("\nBuildings:");
+                for (building, level) in entry.buildings.iter() {
+                    details.push_str(&format!("\n  {} {}", building, level));
+                }
+            }
+            details
+        }
+    };
+
+    graphics::Text::new(
+        graphics::TextFragment::new(details).color(graphics::Color::from_rgb_u32(PALETTE[6])),
+    )
+    .set_font("Retro")
+    .set_scale(20.)
+    .to_owned()
+    .to_element_builder(DETAILS_ID, ctx)
+    .with_visuals(super::BUTTON_VIS)
+    .with_padding((10., 10., 10., 10.))
+    .build()
+}
@@ -0,0 +1,270 @@
+use std::{cell::RefCell, rc::Rc};
+
+use ggez::{graphics, GameError};
+use mooeye::{scene_manager, ui, ui::UiContent};
+
+use crate::PALETTE;
+
+use super::super::game_state::{self, difficulty::Difficulty, mutators::Mutator};
+
+const LIST_CONTAINER_ID: u32 = 10;
+const LIST_ID: u32 = 20;
+const MUTATOR_IDS: u32 = 21;
+const DIFFICULTY_ID: u32 = 3;
+
+/// A menu shown before starting a run, allowing the player to choose the difficulty and toggle the mutators of the run.
+pub struct MutatorMenu {
+    gui: ui::UiElement<()>,
+    /// The difficulty currently selected.
+    difficulty: Difficulty,
+    /// The mutators currently selected.
+    selected: Vec<Mutator>,
+    /// The config of the run to start is written here once the player starts the run.
+    start_request: Rc<RefCell<Option<game_state::GameConfig>>>,
+}
+
+impl MutatorMenu {
+    pub fn new(
+        ctx: &ggez::Context,
+        start_request: Rc<RefCell<Option<game_state::GameConfig>>>,
+    ) -> Result<Self, GameError> {
+        // title
+
+        let title = graphics::Text::new(
+            graphics::TextFragment::new("New Run").color(graphics::Color::from_rgb_u32(PALETTE[8])),
+        )
+        .set_font("Retro")
+        .set_scale(48.)
+        .to_owned()
+        .to_element(0, ctx);
+
+        let info = graphics::Text::new(
+            graphics::TextFragment::new("Make your run harder to earn a higher score.")
+                .color(graphics::Color::from_rgb_u32(PALETTE[7])),
+        )
+        .set_font("Retro")
+        .set_scale(24.)
+        .to_owned()
+        .to_element(0, ctx);
+
+        let start = graphics::Text::new(
+            graphics::TextFragment::new("Start").color(graphics::Color::from_rgb_u32(PALETTE[6])),
+        )
+        .set_font("Retro")
+        .set_scale(32.)
+        .to_owned()
+        .to_element_builder(1, ctx)
+        .with_trigger_key(ggez::winit::event::VirtualKeyCode::S)
+        .with_visuals(super::BUTTON_VIS)
+        .with_hover_visuals(super::BUTTON_HOVER_VIS)
+        .with_trigger_sound(ggez::audio::Source::new(ctx, "/audio/sounds/ui/blipSelect.wav").ok())
+        .build();
+
+        let back = graphics::Text::new(
+            graphics::TextFragment::new("Close").color(graphics::Color::from_rgb_u32(PALETTE[6])),
+        )
+        .set_font("Retro")
+        .set_scale(32.)
+        .to_owned()
+        .to_element_builder(2, ctx)
+        .with_trigger_key(ggez::winit::event::VirtualKeyCode::C)
+        .with_visuals(super::BUTTON_VIS)
+        .with_hover_visuals(super::BUTTON_HOVER_VIS)
+        .with_trigger_sound(ggez::audio::Source::new(ctx, "/audio/sounds/ui/blipSelect.wav").ok())
+        .build();
+
+        // Container
+
+        let menu_box = ui::containers::VerticalBox::new_spaced(25.)
+            .to_element_builder(0, ctx)
+            .with_child(title)
+            .with_child(info)
+            .with_child(
+                ui::containers::StackBox::new()
+                    .to_element_builder(LIST_CONTAINER_ID, ctx)
+                    .with_child(create_mutator_list(ctx, Difficulty::Normal, &[]))
+                    .with_wrapper_layout(ui::Layout::default())
+                    .build(),
+            )
+            .with_child(start)
+            .with_child(back)
+            .with_visuals(super::BUTTON_VIS)
+            .with_alignment(ui::Alignment::Center, ui::Alignment::Center)
+            .with_padding((25., 25., 25., 25.))
+            .build();
+
+        Ok(Self {
+            gui: menu_box,
+            difficulty: Difficulty::Normal,
+            selected: Vec::new(),
+            start_request,
+        })
+    }
+}
+
+impl scene_manager::Scene for MutatorMenu {
+    fn update(
+        &mut self,
+        ctx: &mut ggez::Context,
+    ) -> Result<mooeye::scene_manager::SceneSwitch, GameError> {
+        let messages = self.gui.manage_messages(ctx, None);
+
+        // toggle mutators
+
+        let mut rebuild_list = false;
+
+        if messages.contains(&ui::UiMessage::Triggered(DIFFICULTY_ID)) {
+            self.difficulty = self.difficulty.next();
+            rebuild_list = true;
+        }
+
+        for (index, mutator) in Mutator::ALL.iter().enumerate() {
+            if messages.contains(&ui::UiMessage::Triggered(MUTATOR_IDS + index as u32)) {
+                if let Some(pos) = self.selected.iter().position(|m| m == mutator) {
+                    self.selected.remove(pos);
+                } else {
+                    self.selected.push(*mutator);
+                }
+                rebuild_list = true;
+            }
+        }
+
+        if rebuild_list {
+            self.gui.remove_elements(LIST_ID);
+            self.gui.add_element(
+                LIST_CONTAINER_ID,
+                create_mutator_list(ctx, self.difficulty, &self.selected),
+            );
+        }
+
+        // start the run
+
+        if messages.contains(&ui::UiMessage::Triggered(1)) {
+            *self.start_request.borrow_mut() = Some(self.selected.iter().fold(
+                game_state::GameConfig::default().with_difficulty(self.difficulty),
+                |config, mutator| config.with_mutator(*mutator),
+            ));
+            return Ok(scene_manager::SceneSwitch::Pop(1));
+        }
+
+        if messages.contains(&ui::UiMessage::Triggered(2)) {
+            Ok(scene_manager::SceneSwitch::Pop(1))
+        } else {
+            Ok(scene_manager::SceneSwitch::None)
+        }
+    }
+
+    fn draw(&mut self, ctx: &mut ggez::Context, mouse_listen: bool) -> Result<(), GameError> {
+        let mut canvas = graphics::Canvas::from_frame(ctx, None);
+        canvas.set_sampler(graphics::Sampler::nearest_clamp());
+
+        self.gui.draw_to_screen(ctx, &mut canvas, mouse_listen);
+
+        canvas.finish(ctx)?;
+        Ok(())
+    }
+}
+
+/// Creates a button cycling through the difficulties, a list of toggle buttons for all mutators highlighting the selected ones, and the resulting score multiplier.
+fn create_mutator_list(
+    ctx: &ggez::Context,
+    difficulty: Difficulty,
+    selected: &[Mutator],
+) -> ui::UiElement<()> {
+    let mut list = ui::containers::VerticalBox::new_spaced(10.)
+        .to_element_builder(LIST_ID, ctx)
+        .with_child(
+            graphics::Text::new(
+                graphics::TextFragment::new(format!("Difficulty: {}", difficulty.name()))
+                    .color(graphics::Color::from_rgb_u32(PALETTE[6])),
+            )
+            .set_font("Retro")
+            .set_scale(28.)
+            .to_owned()
+            .to_element_builder(DIFFICULTY_ID, ctx)
+            .with_trigger_key(ggez::winit::event::VirtualKeyCode::D)
+            .with_visuals(super::BUTTON_VIS)
+            .with_hover_visuals(super::BUTTON_HOVER_VIS)
+            .with_trigger_sound(
+                ggez::audio::Source::new(ctx, "/audio/sounds/ui/blipSelect.wav").ok(),
+            )
+            .with_tooltip(
+                graphics::Text::new(
+                    graphics::TextFragment::new(format!(
+                        "Enemy health x{:.2}, bounty x{:.2}, city health x{:.2}.",
+                        difficulty.enemy_health_factor(),
+                        difficulty.bounty_factor(),
+                        difficulty.city_health_factor()
+                    ))
+                    .color(graphics::Color::from_rgb_u32(PALETTE[6])),
+                )
+                .set_scale(24.)
+                .set_font("Retro")
+                .to_owned()
+                .to_element_builder(0, ctx)
+                .with_visuals(super::BUTTON_VIS)
+                .build(),
+            )
+            .build(),
+        );
+
+    for (index, mutator) in Mutator::ALL.iter().enumerate() {
+        let active = selected.contains(mutator);
+        list = list.with_child(
+            graphics::Text::new(
+                graphics::TextFragment::new(format!(
+                    "[{}] {}",
+                    if active { "X" } else { " " },
+                    mutator.name()
+                ))
+                .color(graphics::Color::from_rgb_u32(if active {
+                    PALETTE[8]
+                } else {
+                    PALETTE[6]
+                })),
+            )
+            .add(
+                graphics::TextFragment::new(format!(" x{:.2}", mutator.score_factor()))
+                    .color(graphics::Color::from_rgb_u32(PALETTE[7]))
+                    .scale(20.),
+            )
+            .set_font("Retro")
+            .set_scale(28.)
+            .to_owned()
+            .to_element_builder(MUTATOR_IDS + index as u32, ctx)
+            .with_visuals(super::BUTTON_VIS)
+            .with_hover_visuals(super::BUTTON_HOVER_VIS)
+            .with_trigger_sound(
+                ggez::audio::Source::new(ctx, "/audio/sounds/ui/blipSelect.wav").ok(),
+            )
+            .with_tooltip(
+                graphics::Text::new(
+                    graphics::TextFragment::new(mutator.description())
+                        .color(graphics::Color::from_rgb_u32(PALETTE[6])),
+                )
+                .set_scale(24.)
+                .set_font("Retro")
+                .to_owned()
+                .to_element_builder(0, ctx)
+                .with_visuals(super::BUTTON_VIS)
+                .build(),
+            )
+            .build(),
+        );
+    }
+
+    list.with_child(
+        graphics::Text::new(
+            graphics::TextFragment::new(format!(
+                "Score Multiplier: x{:.2}",
+                game_state::mutators::score_factor(selected)
+            ))
+            .color(graphics::Color::from_rgb_u32(PALETTE[8])),
+        )
+        .set_font("Retro")
+        .set_scale(28.)
+        .to_owned()
+        .to_element(0, ctx),
+    )
+    .build()
+}
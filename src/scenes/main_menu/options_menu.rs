@@ -74,7 +74,7 @@ impl OptionsMenu {
         .with_trigger_sound(ggez::audio::Source::new(ctx, "/audio/sounds/ui/blipSelect.wav").ok())
         .build();
 
-        let options = options::OPTIONS.with(|opt| *opt.borrow());
+        let options = options::OPTIONS.with(|opt| opt.borrow().clone());
 
         let tutorial = graphics::Text::new(
             graphics::TextFragment::new("Re-enable Tutorial Hints")
@@ -146,8 +146,11 @@ impl OptionsMenu {
 
         Ok(Self {
             gui: options_box,
-            controller: super::game_state::Controller::from_path("./data/keymap.toml")
-                .unwrap_or_default(),
+            controller: crate::storage::load(
+                crate::paths::save_path("keymap.toml"),
+                "key bindings",
+            )
+            .unwrap_or_default(),
             options,
         })
     }
@@ -240,11 +243,15 @@ impl scene_manager::Scene for OptionsMenu {
         // Exit options
 
         if messages.contains(&ui::UiMessage::Triggered(3)) {
-            if self.controller.save_to_file("./data/keymap.toml").is_err() {
+            if self
+                .controller
+                .save_to_file(crate::paths::save_path("keymap.toml"))
+                .is_err()
+            {
                 println!("[WARNING] Could not save keybindings.")
             }
             // save internally
-            options::OPTIONS.with(|opt| *opt.borrow_mut() = self.options);
+            options::OPTIONS.with(|opt| *opt.borrow_mut() = self.options.clone());
 
             Ok(mooeye::scene_manager::SceneSwitch::Pop(1))
         } else {
@@ -263,7 +270,13 @@ impl scene_manager::Scene for OptionsMenu {
     }
 }
 
-fn create_sound_adjuster(ctx: &ggez::Context, id_start: u32, value: u8) -> ui::UiElement<()> {
+/// Creates a row of buttons for decreasing and increasing a value in small and large steps, displaying the current value in between.
+/// The buttons use the ids following `id_start`, from large decrease to large increase.
+pub(super) fn create_sound_adjuster(
+    ctx: &ggez::Context,
+    id_start: u32,
+    value: impl std::fmt::Display,
+) -> ui::UiElement<()> {
     ui::containers::HorizontalBox::new_spaced(0.)
         .to_element_builder(id_start, ctx)
         .with_child(
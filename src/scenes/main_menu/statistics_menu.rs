@@ -0,0 +1,201 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use ggez::{graphics, GameError};
+use mooeye::{scene_manager, ui, ui::UiContainer, ui::UiContent};
+
+use super::game_state::{achievements, statistics::LifetimeStats};
+use crate::PALETTE;
+
+/// The size of the section titles.
+const TITLE_SIZE: f32 = 28.;
+/// The size of the listed statistics.
+const ENTRY_SIZE: f32 = 20.;
+
+/// A menu displaying the statistics accumulated over all runs.
+pub struct StatisticsMenu {
+    gui: ui::UiElement<()>,
+}
+
+impl StatisticsMenu {
+    pub fn new(ctx: &ggez::Context) -> Result<Self, GameError> {
+        // title
+
+        let title = graphics::Text::new(
+            graphics::TextFragment::new("Statistics")
+                .color(graphics::Color::from_rgb_u32(PALETTE[8])),
+        )
+        .set_font("Retro")
+        .set_scale(48.)
+        .to_owned()
+        .to_element(0, ctx);
+
+        // statistics
+
+        let statistics = achievements::STATISTICS.with(|statistics| statistics.borrow().clone());
+
+        let mut general = graphics::Text::default();
+        add_section(
+            &mut general,
+            "General",
+            vec![
+                ("Runs".to_owned(), statistics.runs.to_string()),
+                ("Playtime".to_owned(), statistics.format_playtime()),
+                ("Waves cleared".to_owned(), statistics.waves.to_string()),
+                (
+                    "Enemies killed".to_owned(),
+                    statistics.total_kills().to_string(),
+                ),
+                ("Gold earned".to_owned(), statistics.gold_earned.to_string()),
+                ("Gold spent".to_owned(), statistics.gold_spent.to_string()),
+            ],
+        );
+        add_section(
+            &mut general,
+            "Buildings built / lost",
+            statistics
+                .buildings_built
+                .keys()
+                .chain(statistics.buildings_lost.keys())
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .map(|building| {
+                    (
+                        building.clone(),
+                        format!(
+                            "{} / {}",
+                            statistics.buildings_built.get(building).unwrap_or(&0),
+                            statistics.buildings_lost.get(building).unwrap_or(&0)
+                        ),
+                    )
+                })
+                .collect(),
+        );
+
+        let mut kills = graphics::Text::default();
+        add_section(&mut kills, "Enemies killed", by_count(&statistics.kills));
+
+        let mut spells = graphics::Text::default();
+        add_section(
+            &mut spells,
+            "Spells cast / damage",
+            spell_entries(&statistics),
+        );
+
+        let mut columns = ui::containers::HorizontalBox::new_spaced(40.);
+        for mut column in [general, kills, spells] {
+            columns.add(
+                column
+                    .set_font("Retro")
+                    .to_owned()
+                    .to_element_builder(0, ctx)
+                    .with_alignment(ui::Alignment::Min, ui::Alignment::Min)
+                    .build(),
+            );
+        }
+        let columns = columns.to_element(0, ctx);
+
+        let back = graphics::Text::new(
+            graphics::TextFragment::new("Close").color(graphics::Color::from_rgb_u32(PALETTE[6])),
+        )
+        .set_font("Retro")
+        .set_scale(32.)
+        .to_owned()
+        .to_element_builder(1, ctx)
+        .with_trigger_key(ggez::winit::event::VirtualKeyCode::C)
+        .with_visuals(super::BUTTON_VIS)
+        .with_hover_visuals(super::BUTTON_HOVER_VIS)
+        .with_trigger_sound(ggez::audio::Source::new(ctx, "/audio/sounds/ui/blipSelect.wav").ok())
+        .build();
+
+        // Container
+
+        let mut statistics_box = ui::containers::VerticalBox::new();
+        statistics_box.add(title);
+        statistics_box.add(columns);
+        statistics_box.add(back);
+        statistics_box.spacing = 25.;
+        let statistics_box = statistics_box
+            .to_element_builder(0, ctx)
+            .with_visuals(super::BUTTON_VIS)
+            .with_alignment(ui::Alignment::Max, ui::Alignment::Center)
+            .with_offset(-25., 0.)
+            .with_padding((25., 25., 25., 25.))
+            .build();
+
+        Ok(Self {
+            gui: statistics_box,
+        })
+    }
+}
+
+impl scene_manager::Scene for StatisticsMenu {
+    fn update(
+        &mut self,
+        ctx: &mut ggez::Context,
+    ) -> Result<scene_manager::SceneSwitch, ggez::GameError> {
+        let messages = self.gui.manage_messages(ctx, None);
+
+        if messages.contains(&ui::UiMessage::Triggered(1)) {
+            Ok(scene_manager::SceneSwitch::Pop(1))
+        } else {
+            Ok(scene_manager::SceneSwitch::None)
+        }
+    }
+
+    fn draw(&mut self, ctx: &mut ggez::Context, mouse_listen: bool) -> Result<(), ggez::GameError> {
+        let mut canvas = graphics::Canvas::from_frame(ctx, None);
+        canvas.set_sampler(graphics::Sampler::nearest_clamp());
+
+        self.gui.draw_to_screen(ctx, &mut canvas, mouse_listen);
+
+        canvas.finish(ctx)?;
+        Ok(())
+    }
+}
+
+/// Adds a section with the passed title and named values to the passed text.
+fn add_section(text: &mut graphics::Text, title: &str, entries: Vec<(String, String)>) {
+    if !text.fragments().is_empty() {
+        text.add(graphics::TextFragment::new("\n").scale(ENTRY_SIZE));
+    }
+    text.add(
+        graphics::TextFragment::new(format!("{}\n", title))
+            .color(graphics::Color::from_rgb_u32(PALETTE[7]))
+            .scale(TITLE_SIZE),
+    );
+    if entries.is_empty() {
+        text.add(
+            graphics::TextFragment::new("  None yet\n")
+                .color(graphics::Color::from_rgb_u32(PALETTE[6]))
+                .scale(ENTRY_SIZE),
+        );
+    }
+    for (name, value) in entries {
+        text.add(
+            graphics::TextFragment::new(format!("  {}: {}\n", name, value))
+                .color(graphics::Color::from_rgb_u32(PALETTE[6]))
+                .scale(ENTRY_SIZE),
+        );
+    }
+}
+
+/// Returns the entries of the passed totals, the largest first.
+fn by_count(totals: &BTreeMap<String, u64>) -> Vec<(String, String)> {
+    let mut entries: Vec<_> = totals.iter().collect();
+    entries.sort_by(|(_, a), (_, b)| b.cmp(a));
+    entries
+        .into_iter()
+        .map(|(name, count)| (name.clone(), count.to_string()))
+        .collect()
+}
+
+/// Returns the casts and damage of every spell ever cast, the most cast first.
+fn spell_entries(statistics: &LifetimeStats) -> Vec<(String, String)> {
+    by_count(&statistics.casts)
+        .into_iter()
+        .map(|(spell, casts)| {
+            let damage = statistics.damage.get(&spell).copied().unwrap_or_default();
+            (spell, format!("{} / {}", casts, damage))
+        })
+        .collect()
+}
@@ -2,6 +2,50 @@ pub mod game_over_menu;
 pub mod game_state;
 pub mod main_menu;
 
+use ggez::winit::event::VirtualKeyCode;
+
+/// The keys that can be typed into names, and the characters they produce.
+const NAME_KEYS: [(VirtualKeyCode, char); 38] = [
+    (VirtualKeyCode::A, 'a'),
+    (VirtualKeyCode::B, 'b'),
+    (VirtualKeyCode::C, 'c'),
+    (VirtualKeyCode::D, 'd'),
+    (VirtualKeyCode::E, 'e'),
+    (VirtualKeyCode::F, 'f'),
+    (VirtualKeyCode::G, 'g'),
+    (VirtualKeyCode::H, 'h'),
+    (VirtualKeyCode::I, 'i'),
+    (VirtualKeyCode::J, 'j'),
+    (VirtualKeyCode::K, 'k'),
+    (VirtualKeyCode::L, 'l'),
+    (VirtualKeyCode::M, 'm'),
+    (VirtualKeyCode::N, 'n'),
+    (VirtualKeyCode::O, 'o'),
+    (VirtualKeyCode::P, 'p'),
+    (VirtualKeyCode::Q, 'q'),
+    (VirtualKeyCode::R, 'r'),
+    (VirtualKeyCode::S, 's'),
+    (VirtualKeyCode::T, 't'),
+    (VirtualKeyCode::U, 'u'),
+    (VirtualKeyCode::V, 'v'),
+    (VirtualKeyCode::W, 'w'),
+    (VirtualKeyCode::X, 'x'),
+    (VirtualKeyCode::Y, 'y'),
+    (VirtualKeyCode::Z, 'z'),
+    (VirtualKeyCode::Key0, '0'),
+    (VirtualKeyCode::Key1, '1'),
+    (VirtualKeyCode::Key2, '2'),
+    (VirtualKeyCode::Key3, '3'),
+    (VirtualKeyCode::Key4, '4'),
+    (VirtualKeyCode::Key5, '5'),
+    (VirtualKeyCode::Key6, '6'),
+    (VirtualKeyCode::Key7, '7'),
+    (VirtualKeyCode::Key8, '8'),
+    (VirtualKeyCode::Key9, '9'),
+    (VirtualKeyCode::Minus, '-'),
+    (VirtualKeyCode::Space, '_'),
+];
+
 const BUTTON_VIS: mooeye::ui::Visuals = mooeye::ui::Visuals {
     background: {
         let c = crate::PALETTE[0].to_be_bytes();
@@ -47,3 +91,19 @@ const BUTTON_HOVER_VIS: mooeye::ui::Visuals = mooeye::ui::Visuals {
     border_widths: [3.; 4],
     corner_radii: [3.; 4],
 };
+
+/// Appends the characters typed during the last frame to the passed name, as long as it is shorter than the passed length, and removes the last character on backspace.
+/// Returns wether the name changed.
+fn type_name(ctx: &ggez::Context, name: &mut String, max_length: usize) -> bool {
+    let mut changed = false;
+    for (key, character) in NAME_KEYS {
+        if ctx.keyboard.is_key_just_pressed(key) && name.len() < max_length {
+            name.push(character);
+            changed = true;
+        }
+    }
+    if ctx.keyboard.is_key_just_pressed(VirtualKeyCode::Back) && name.pop().is_some() {
+        changed = true;
+    }
+    changed
+}
@@ -0,0 +1,240 @@
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+/// The key the schema version of a save file is kept under.
+const VERSION_KEY: &str = "version";
+
+/// Problems with save files that have not yet been shown to the player.
+static WARNINGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Save files written by a newer version of the game. These are never overwritten or removed, so updating the game restores them.
+static LOCKED: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+/// The error of a save file that was written by a newer version of the game than this one.
+#[derive(Debug)]
+pub struct NewerVersion {
+    /// The schema version the file was written with.
+    version: u32,
+    /// The newest schema version this version of the game can read.
+    expected: u32,
+}
+
+impl std::fmt::Display for NewerVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The file was written by a newer version of the game (version {}, expected at most {}).",
+            self.version, self.expected
+        )
+    }
+}
+
+impl std::error::Error for NewerVersion {}
+
+/// A type that is persisted to a save file. Every save file carries the schema version it was written with,
+/// and files written with older versions are upgraded one version at a time before being parsed.
+pub trait SaveFile: Serialize + DeserializeOwned {
+    /// The current schema version of this file. Needs to be increased, and a migration added,
+    /// whenever the format changes in a way files written with the previous version can't be parsed anymore.
+    const VERSION: u32;
+
+    /// Upgrades the passed contents of a file written with the passed version to the next version.
+    /// Version 0 are files without a version, as written by the game up to version 1.3.0.
+    fn migrate(_from: u32, _contents: &mut toml::Table) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Writes the passed contents to the passed path. The contents are first written to a temporary file that then replaces the old file,
+/// so a crash mid-write never leaves a partially written file behind. The previous version of the file is kept as a backup.
+/// Fails for files written by a newer version of the game, see [load].
+pub fn write_atomic(path: impl AsRef<Path>, contents: &str) -> std::io::Result<()> {
+    let path = path.as_ref();
+    check_unlocked(path)?;
+    let temp_path = with_suffix(path, "tmp");
+
+    let mut file = fs::File::create(&temp_path)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    drop(file);
+
+    if path.exists() {
+        fs::copy(path, with_suffix(path, "bak"))?;
+    }
+    fs::rename(&temp_path, path)
+}
+
+/// Serializes the passed value along with its schema version and writes it to the passed path, as described in [write_atomic].
+pub fn save<T: SaveFile>(
+    path: impl AsRef<Path>,
+    value: &T,
+) -> Result<(), Box<dyn std::error::Error>> {
+    write_atomic(
+        path,
+        &format!(
+            "{} = {}\n{}",
+            VERSION_KEY,
+            T::VERSION,
+            toml::to_string(value)?
+        ),
+    )?;
+    Ok(())
+}
+
+/// Reads the save file at the passed path and upgrades it to the current schema version, failing if it does not exist or is empty.
+/// Unlike [load], the file is not replaced by its backup if it can't be read.
+pub fn read<T: SaveFile>(path: impl AsRef<Path>) -> Result<T, Box<dyn std::error::Error>> {
+    parse(path.as_ref())?.ok_or_else(|| "The file is empty.".into())
+}
+
+/// Loads and parses the save file at the passed path. Returns None if the file does not exist (yet) or is empty.
+/// If the file can't be read or parsed, it is kept with a .corrupt suffix and its backup is restored and loaded instead.
+/// If there is no valid backup either, None is returned. In both cases, a warning is queued to be shown to the player.
+/// A file written by a newer version of the game is left untouched instead, None is returned and the file is not saved to or removed until the game is updated.
+pub fn load<T: SaveFile>(path: impl AsRef<Path>, description: &str) -> Option<T> {
+    let path = path.as_ref();
+    if !path.exists() {
+        return None;
+    }
+
+    let error = match parse(path) {
+        Ok(value) => return value,
+        Err(e) => e,
+    };
+    println!(
+        "[ERROR/Radish] Could not read {}: {}",
+        path.display(),
+        error
+    );
+
+    if error.is::<NewerVersion>() {
+        if let Ok(mut locked) = LOCKED.lock() {
+            locked.push(path.to_owned());
+        }
+        warn(format!(
+            "Could not read your {}, as a newer version of the game wrote the file. Update the game to use it, until then it is not saved to.",
+            description
+        ));
+        return None;
+    }
+
+    // keep the unreadable file around instead of overwriting it on the next save
+    if let Err(e) = fs::rename(path, with_suffix(path, "corrupt")) {
+        println!(
+            "[ERROR/Radish] Could not keep unreadable file {}: {}",
+            path.display(),
+            e
+        );
+    }
+
+    let backup_path = with_suffix(path, "bak");
+    match parse(&backup_path) {
+        Ok(Some(value)) => {
+            if let Err(e) = fs::copy(&backup_path, path) {
+                println!(
+                    "[ERROR/Radish] Could not restore backup {}: {}",
+                    backup_path.display(),
+                    e
+                );
+            }
+            warn(format!(
+                "Could not read your {}, restored the backup instead.",
+                description
+            ));
+            Some(value)
+        }
+        _ => {
+            warn(format!(
+                "Could not read your {}, no valid backup was found.",
+                description
+            ));
+            None
+        }
+    }
+}
+
+/// Removes the save file at the passed path along with its backup.
+/// Fails for files written by a newer version of the game, see [load].
+pub fn remove(path: impl AsRef<Path>) -> std::io::Result<()> {
+    let path = path.as_ref();
+    check_unlocked(path)?;
+    let backup_path = with_suffix(path, "bak");
+    if backup_path.exists() {
+        fs::remove_file(backup_path)?;
+    }
+    fs::remove_file(path)
+}
+
+/// Queues a warning about a save file to be shown to the player.
+pub fn warn(message: String) {
+    println!("[WARNING] {}", message);
+    if let Ok(mut warnings) = WARNINGS.lock() {
+        warnings.push(message);
+    }
+}
+
+/// Returns all warnings about save files that have not yet been shown to the player and clears them.
+pub fn take_warnings() -> Vec<String> {
+    WARNINGS
+        .lock()
+        .map(|mut warnings| std::mem::take(&mut *warnings))
+        .unwrap_or_default()
+}
+
+/// Reads and parses the file at the passed path, upgrading it to the current schema version first. An empty file is treated as no contents.
+fn parse<T: SaveFile>(path: &Path) -> Result<Option<T>, Box<dyn std::error::Error>> {
+    let string = fs::read_to_string(path)?;
+    if string.trim().is_empty() {
+        return Ok(None);
+    }
+
+    let mut contents: toml::Table = toml::from_str(&string)?;
+    let version = match contents.remove(VERSION_KEY) {
+        None => 0,
+        Some(toml::Value::Integer(version)) => {
+            u32::try_from(version).map_err(|_| format!("Invalid version {}.", version))?
+        }
+        Some(version) => return Err(format!("Invalid version {}.", version).into()),
+    };
+    if version > T::VERSION {
+        return Err(NewerVersion {
+            version,
+            expected: T::VERSION,
+        }
+        .into());
+    }
+
+    for from in version..T::VERSION {
+        T::migrate(from, &mut contents)?;
+    }
+    Ok(Some(toml::Value::Table(contents).try_into()?))
+}
+
+/// Fails if the file at the passed path was written by a newer version of the game and must not be changed.
+fn check_unlocked(path: &Path) -> std::io::Result<()> {
+    if LOCKED
+        .lock()
+        .map(|locked| locked.iter().any(|locked| locked == path))
+        .unwrap_or_default()
+    {
+        return Err(std::io::Error::other(format!(
+            "{} was written by a newer version of the game.",
+            path.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Returns the passed path with the passed suffix appended to its file name.
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_owned();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}